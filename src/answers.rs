@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::report::title;
+use crate::runner::{RunResult, Solution};
+use crate::util::{parse_token, split_pair, strip_prefix, strip_suffix, ParseError};
+
+/// A manifest of expected answers, read from a TOML-like file:
+///
+/// ```toml
+/// year = 2020
+///
+/// [day1]
+/// part1 = 1234
+/// part2 = "abc"
+/// ```
+///
+/// Comments start with `#`. Answers can be integers or double-quoted strings.
+/// The year must come before the first section, manifests without a year are for 2020.
+#[derive(Debug, Default, Clone)]
+pub struct Answers {
+    answers: HashMap<(u16, u8, u8), String>,
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Answers, Box<dyn Error>> {
+        let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Ok(contents.parse().map_err(|e| format!("{}: {}", path, e))?)
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(|x| x.as_str())
+    }
+
+    pub fn insert(&mut self, year: u16, day: u8, part: u8, answer: String) {
+        self.answers.insert((year, day, part), answer);
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+        let mut year = 2020;
+        let mut day = None;
+        for (i, line) in s.lines().enumerate() {
+            parse_line(line, &mut year, &mut day, &mut answers).map_err(|e| e.offset_lines(i))?;
+        }
+        Ok(answers)
+    }
+}
+
+fn parse_line(
+    line: &str,
+    year: &mut u16,
+    day: &mut Option<u8>,
+    answers: &mut Answers,
+) -> Result<(), ParseError> {
+    let line = strip_comment(line).trim();
+    if line.is_empty() {
+        return Ok(());
+    }
+    if let Some(section) = line.strip_prefix('[') {
+        let section = strip_suffix(line, section, "]")?;
+        let section = strip_prefix(line, section, "day")?;
+        *day = Some(parse_token(line, section, "day number")?);
+        return Ok(());
+    }
+    let (key, value) = split_pair(line, line, "=")?;
+    if day.is_none() && key.trim() == "year" {
+        *year = parse_token(line, value.trim(), "year")?;
+        return Ok(());
+    }
+    let day = day.ok_or_else(|| ParseError::at(line, line, "`[dayN]` section"))?;
+    let part = strip_prefix(line, key.trim(), "part")?;
+    let part = parse_token(line, part, "part number")?;
+    let value = value.trim();
+    let answer = match value.strip_prefix('"') {
+        Some(quoted) => strip_suffix(line, quoted, "\"")?.to_owned(),
+        None => parse_token::<i64>(line, value, "integer or string")?.to_string(),
+    };
+    answers.insert(*year, day, part, answer);
+    Ok(())
+}
+
+/// Removes a `#` comment from a line, unless the `#` is inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// A problem found while checking the results of the solutions against the expected answers.
+#[derive(Debug)]
+pub enum CheckFailure<'a> {
+    /// The solution returned an error.
+    Failed {
+        solution: &'a Solution,
+        error: String,
+    },
+    /// There is no expected answer for this solution in the manifest.
+    Missing { solution: &'a Solution },
+    /// The solution returned a different answer than the expected one.
+    Wrong {
+        solution: &'a Solution,
+        expected: String,
+        actual: String,
+    },
+    /// The solution returned a different answer than another solution for the same day and part.
+    Disagrees {
+        solution: &'a Solution,
+        actual: String,
+        sibling: &'a Solution,
+        sibling_answer: String,
+    },
+}
+
+impl Display for CheckFailure<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckFailure::Failed { solution, error } => {
+                write!(f, "{}: failed: {}", title(solution), error)
+            }
+            CheckFailure::Missing { solution } => {
+                write!(f, "{}: no expected answer", title(solution))
+            }
+            CheckFailure::Wrong {
+                solution,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: expected {}, but got {}",
+                title(solution),
+                expected,
+                actual
+            ),
+            CheckFailure::Disagrees {
+                solution,
+                actual,
+                sibling,
+                sibling_answer,
+            } => write!(
+                f,
+                "{}: got {}, but {} got {}",
+                title(solution),
+                actual,
+                title(sibling),
+                sibling_answer
+            ),
+        }
+    }
+}
+
+/// Checks the results of running the given solutions against the expected answers,
+/// and against the results of the other solutions for the same day and part.
+pub fn check<'a>(
+    answers: &Answers,
+    results: &[(&'a Solution, RunResult)],
+) -> Vec<CheckFailure<'a>> {
+    let mut failures = vec![];
+    for (i, &(solution, ref result)) in results.iter().enumerate() {
+        let actual = match result {
+            Ok(output) => output.answer.to_string(),
+            Err(e) => {
+                failures.push(CheckFailure::Failed {
+                    solution,
+                    error: e.to_string(),
+                });
+                continue;
+            }
+        };
+        match answers.get(solution.year, solution.day, solution.part) {
+            Some(expected) if expected != actual => failures.push(CheckFailure::Wrong {
+                solution,
+                expected: expected.to_owned(),
+                actual: actual.clone(),
+            }),
+            Some(_) => {}
+            None => failures.push(CheckFailure::Missing { solution }),
+        }
+        // Compare with the first successful sibling solution
+        let sibling = results[..i]
+            .iter()
+            .find_map(|(sibling, result)| match result {
+                Ok(output)
+                    if sibling.year == solution.year
+                        && sibling.day == solution.day
+                        && sibling.part == solution.part =>
+                {
+                    Some((*sibling, &output.answer))
+                }
+                _ => None,
+            });
+        if let Some((sibling, sibling_answer)) = sibling {
+            if sibling_answer.to_string() != actual {
+                failures.push(CheckFailure::Disagrees {
+                    solution,
+                    actual: actual.clone(),
+                    sibling,
+                    sibling_answer: sibling_answer.to_string(),
+                });
+            }
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{find_solutions, Answer, Output};
+    use std::time::Duration;
+
+    fn output(answer: &str) -> RunResult {
+        Ok(Output {
+            answer: Answer::from(answer),
+            generator_time: Duration::default(),
+            solver_time: Duration::default(),
+        })
+    }
+
+    #[test]
+    fn parse_manifest() {
+        let answers =
+            "# comment\n[day1]\npart1 = 42\npart2 = \"a,b\" # trailing\n\n[day2]\npart1 = 7"
+                .parse::<Answers>()
+                .unwrap();
+        assert_eq!(answers.get(2020, 1, 1), Some("42"));
+        assert_eq!(answers.get(2020, 1, 2), Some("a,b"));
+        assert_eq!(answers.get(2020, 2, 1), Some("7"));
+        assert_eq!(answers.get(2020, 2, 2), None);
+
+        let answers = "year = 2021\n[day1]\npart1 = 42"
+            .parse::<Answers>()
+            .unwrap();
+        assert_eq!(answers.get(2021, 1, 1), Some("42"));
+        assert_eq!(answers.get(2020, 1, 1), None);
+    }
+
+    #[test]
+    fn parse_manifest_error() {
+        let error = "[day1]\npart1 = 42\npart2 = abc"
+            .parse::<Answers>()
+            .unwrap_err();
+        assert_eq!(error.line(), Some(3));
+    }
+
+    #[test]
+    fn check_siblings() {
+        let mut answers = Answers::default();
+        answers.insert(2020, 13, 2, "100".to_owned());
+        let solutions = find_solutions(Some(2020), Some(13), Some(2), None).collect::<Vec<_>>();
+        let results = vec![(solutions[0], output("100")), (solutions[1], output("101"))];
+        let failures = check(&answers, &results);
+        assert_eq!(failures.len(), 2);
+        assert!(matches!(failures[0], CheckFailure::Wrong { .. }));
+        assert!(matches!(failures[1], CheckFailure::Disagrees { .. }));
+    }
+
+    /// Checks all solutions against the expected answers for the real puzzle inputs.
+    /// Some solutions are slow in debug builds, run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn check_all_answers() {
+        let answers = Answers::load("input/2020/answers.toml").unwrap();
+        let results = crate::runner::solutions()
+            .iter()
+            .map(|solution| {
+                let input = std::fs::read_to_string(format!("input/2020/day{}.txt", solution.day));
+                (
+                    solution,
+                    input
+                        .map_err(|e| e.into())
+                        .and_then(|input| solution.run(&input)),
+                )
+            })
+            .collect::<Vec<_>>();
+        let failures = check(&answers, &results);
+        for failure in &failures {
+            eprintln!("{}", failure);
+        }
+        assert!(failures.is_empty());
+    }
+}
@@ -0,0 +1,477 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::report::title_of;
+use crate::runner::{InputMode, Solution};
+use crate::util::{parse_lines, parse_token, ParseError};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// A global allocator which counts all allocations, so that benchmarks can report them.
+///
+/// The library does not install it, register it in the binary instead:
+///
+/// ```
+/// use advent_of_code_2020::bench::CountingAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// # fn main() {}
+/// ```
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn count(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        CountingAllocator::count(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        CountingAllocator::count(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        CountingAllocator::count(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Returns the number of allocations and the allocated bytes so far,
+/// or `None` if the [`CountingAllocator`] is not installed.
+fn allocation_counts() -> Option<(u64, u64)> {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    // Every program allocates before it gets here, e.g. for its arguments
+    if allocations == 0 {
+        return None;
+    }
+    Some((allocations, ALLOCATED_BYTES.load(Ordering::Relaxed)))
+}
+
+/// The fastest, median and slowest of a number of timed runs.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct Timings {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    /// Summarizes the given samples. Returns all zeros if there are no samples.
+    pub fn from_samples(samples: &mut [Duration]) -> Timings {
+        samples.sort();
+        match *samples {
+            [] => Timings::default(),
+            [min, .., max] => Timings {
+                min,
+                median: samples[samples.len() / 2],
+                max,
+            },
+            [only] => Timings {
+                min: only,
+                median: only,
+                max: only,
+            },
+        }
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} / {:?} / {:?}", self.min, self.median, self.max)
+    }
+}
+
+/// The timings and allocations of one solution, over a number of runs.
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub name: Option<String>,
+    pub runs: usize,
+    pub generator: Timings,
+    pub solver: Timings,
+    /// The number of allocations and the allocated bytes of a single run,
+    /// if the [`CountingAllocator`] is installed.
+    pub allocations: Option<(u64, u64)>,
+}
+
+impl Measurement {
+    fn key(&self) -> (u16, u8, u8, Option<&str>) {
+        (self.year, self.day, self.part, self.name.as_deref())
+    }
+
+    /// Returns the title of the measured solution, as printed by aoc-runner.
+    pub fn title(&self) -> String {
+        title_of(self.day, self.part, self.name.as_deref())
+    }
+
+    /// The median time of the generator and the solver together.
+    pub fn median(&self) -> Duration {
+        self.generator.median + self.solver.median
+    }
+}
+
+impl Display for Measurement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} runs, min / median / max)\n\tgenerator: {}\n\trunner: {}",
+            self.title(),
+            self.runs,
+            self.generator,
+            self.solver
+        )?;
+        if let Some((allocations, bytes)) = self.allocations {
+            write!(f, "\n\tallocations: {} ({} bytes)", allocations, bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs a solution `runs` times after one warm-up run, and measures every run.
+pub fn measure(
+    solution: &Solution,
+    input: &str,
+    runs: usize,
+    mode: InputMode,
+) -> Result<Measurement, Box<dyn Error + Send + Sync>> {
+    solution.run_with(input, mode)?;
+    let mut generator_times = vec![];
+    let mut solver_times = vec![];
+    let mut allocations = None;
+    for _ in 0..runs {
+        let before = allocation_counts();
+        let output = solution.run_with(input, mode)?;
+        let after = allocation_counts();
+        generator_times.push(output.generator_time);
+        solver_times.push(output.solver_time);
+        if let (Some(before), Some(after)) = (before, after) {
+            let counts = (after.0 - before.0, after.1 - before.1);
+            allocations = Some(allocations.map_or(counts, |min: (u64, u64)| min.min(counts)));
+        }
+    }
+    Ok(Measurement {
+        year: solution.year,
+        day: solution.day,
+        part: solution.part,
+        name: solution.name.map(str::to_owned),
+        runs,
+        generator: Timings::from_samples(&mut generator_times),
+        solver: Timings::from_samples(&mut solver_times),
+        allocations,
+    })
+}
+
+const CSV_HEADER: &str = "year,day,part,solver,runs,\
+generator_min_ns,generator_median_ns,generator_max_ns,\
+solver_min_ns,solver_median_ns,solver_max_ns,allocations,allocated_bytes";
+
+/// The header of baselines saved before the year was added. Their measurements are all of 2020.
+const CSV_HEADER_2020: &str = "day,part,solver,runs,\
+generator_min_ns,generator_median_ns,generator_max_ns,\
+solver_min_ns,solver_median_ns,solver_max_ns,allocations,allocated_bytes";
+
+/// Saved measurements to compare later runs against, stored as CSV with a header line.
+#[derive(Debug, Default, Clone)]
+pub struct Baseline {
+    measurements: Vec<Measurement>,
+}
+
+impl Baseline {
+    pub fn new(measurements: Vec<Measurement>) -> Baseline {
+        Baseline { measurements }
+    }
+
+    pub fn load(path: &str) -> Result<Baseline, Box<dyn Error>> {
+        let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Ok(contents.parse().map_err(|e| format!("{}: {}", path, e))?)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let mut file = std::fs::File::create(path).map_err(|e| format!("{}: {}", path, e))?;
+        self.write(&mut file)?;
+        Ok(())
+    }
+
+    pub fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{}", CSV_HEADER)?;
+        for m in &self.measurements {
+            let (allocations, bytes) = m
+                .allocations
+                .map_or((String::new(), String::new()), |(a, b)| {
+                    (a.to_string(), b.to_string())
+                });
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                m.year,
+                m.day,
+                m.part,
+                m.name.as_deref().unwrap_or(""),
+                m.runs,
+                m.generator.min.as_nanos(),
+                m.generator.median.as_nanos(),
+                m.generator.max.as_nanos(),
+                m.solver.min.as_nanos(),
+                m.solver.median.as_nanos(),
+                m.solver.max.as_nanos(),
+                allocations,
+                bytes
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Compares measurements against this baseline. A solution has regressed if its median time
+    /// grew by more than `tolerance` (e.g. `0.2` for 20%), or if it allocates more often.
+    pub fn compare(&self, measurements: &[Measurement], tolerance: f64) -> Vec<Regression> {
+        let baseline = self
+            .measurements
+            .iter()
+            .map(|m| (m.key(), m))
+            .collect::<HashMap<_, _>>();
+        let mut regressions = vec![];
+        for current in measurements {
+            let baseline = match baseline.get(&current.key()) {
+                Some(baseline) => *baseline,
+                None => continue,
+            };
+            let limit = baseline.median().mul_f64(1.0 + tolerance);
+            if current.median() > limit {
+                regressions.push(Regression::Time {
+                    title: current.title(),
+                    baseline: baseline.median(),
+                    current: current.median(),
+                });
+            }
+            if let (Some((baseline, _)), Some((current_count, _))) =
+                (baseline.allocations, current.allocations)
+            {
+                if current_count > baseline {
+                    regressions.push(Regression::Allocations {
+                        title: current.title(),
+                        baseline,
+                        current: current_count,
+                    });
+                }
+            }
+        }
+        regressions
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rows, year) = match s.split_once('\n') {
+            Some((header, rows)) if header.trim_end() == CSV_HEADER => (rows, None),
+            Some((header, rows)) if header.trim_end() == CSV_HEADER_2020 => (rows, Some(2020)),
+            _ => {
+                let header = s.lines().next().unwrap_or("");
+                return Err(ParseError::at(s, header, "benchmark CSV header").with_line(1));
+            }
+        };
+        let measurements = parse_lines(rows, |line| parse_measurement(line, year))
+            .map_err(|e| e.offset_lines(1))?;
+        Ok(Baseline { measurements })
+    }
+}
+
+/// Parses a line of a baseline. Without a `year`, the year is the first field.
+fn parse_measurement(line: &str, year: Option<u16>) -> Result<Measurement, ParseError> {
+    let mut fields = line.split(',').collect::<Vec<_>>();
+    let year = match year {
+        Some(year) => year,
+        None if fields.len() == 13 => parse_token(line, fields.remove(0), "year")?,
+        None => return Err(ParseError::at(line, line, "13 comma-separated fields")),
+    };
+    if fields.len() != 12 {
+        return Err(ParseError::at(line, line, "12 comma-separated fields"));
+    }
+    let nanos = |field: &str| parse_token(line, field, "nanoseconds").map(Duration::from_nanos);
+    let timings = |fields: &[&str]| -> Result<Timings, ParseError> {
+        Ok(Timings {
+            min: nanos(fields[0])?,
+            median: nanos(fields[1])?,
+            max: nanos(fields[2])?,
+        })
+    };
+    let allocations = match (fields[10], fields[11]) {
+        ("", "") => None,
+        (allocations, bytes) => Some((
+            parse_token(line, allocations, "allocation count")?,
+            parse_token(line, bytes, "byte count")?,
+        )),
+    };
+    Ok(Measurement {
+        year,
+        day: parse_token(line, fields[0], "day number")?,
+        part: parse_token(line, fields[1], "part number")?,
+        name: Some(fields[2])
+            .filter(|name| !name.is_empty())
+            .map(str::to_owned),
+        runs: parse_token(line, fields[3], "number of runs")?,
+        generator: timings(&fields[4..7])?,
+        solver: timings(&fields[7..10])?,
+        allocations,
+    })
+}
+
+/// A solution which got slower or allocates more than in the baseline.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Regression {
+    Time {
+        title: String,
+        baseline: Duration,
+        current: Duration,
+    },
+    Allocations {
+        title: String,
+        baseline: u64,
+        current: u64,
+    },
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Regression::Time {
+                title,
+                baseline,
+                current,
+            } => write!(
+                f,
+                "{}: median time {:?}, baseline {:?} ({:+.1}%)",
+                title,
+                current,
+                baseline,
+                (current.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+            ),
+            Regression::Allocations {
+                title,
+                baseline,
+                current,
+            } => write!(
+                f,
+                "{}: {} allocations, baseline {}",
+                title, current, baseline
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(name: Option<&str>, median_ms: u64, allocations: u64) -> Measurement {
+        let timings = |ms| Timings {
+            min: Duration::from_millis(ms),
+            median: Duration::from_millis(ms),
+            max: Duration::from_millis(ms),
+        };
+        Measurement {
+            year: 2020,
+            day: 13,
+            part: 2,
+            name: name.map(str::to_owned),
+            runs: 5,
+            generator: timings(1),
+            solver: timings(median_ms - 1),
+            allocations: Some((allocations, allocations * 8)),
+        }
+    }
+
+    #[test]
+    fn timings() {
+        let ms = Duration::from_millis;
+        let mut samples = vec![ms(5), ms(1), ms(3), ms(9), ms(2)];
+        let timings = Timings::from_samples(&mut samples);
+        assert_eq!(
+            timings,
+            Timings {
+                min: ms(1),
+                median: ms(3),
+                max: ms(9)
+            }
+        );
+        assert_eq!(Timings::from_samples(&mut []), Timings::default());
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let baseline = Baseline::new(vec![
+            measurement(None, 10, 3),
+            measurement(Some("iterative"), 20, 4),
+        ]);
+        let mut csv = vec![];
+        baseline.write(&mut csv).unwrap();
+        let parsed = String::from_utf8(csv).unwrap().parse::<Baseline>().unwrap();
+        assert_eq!(parsed.measurements.len(), 2);
+        assert_eq!(parsed.measurements[1].name.as_deref(), Some("iterative"));
+        assert_eq!(parsed.measurements[1].median(), Duration::from_millis(20));
+        assert_eq!(parsed.measurements[1].allocations, Some((4, 32)));
+        let error = "day,part\n".parse::<Baseline>().unwrap_err();
+        assert_eq!(error.line(), Some(1));
+    }
+
+    #[test]
+    fn baseline_without_year() {
+        let csv = format!("{}\n13,2,,5,1,1,1,9,9,9,,\n", CSV_HEADER_2020);
+        let parsed = csv.parse::<Baseline>().unwrap();
+        assert_eq!(parsed.measurements[0].year, 2020);
+        assert_eq!(parsed.measurements[0].key(), (2020, 13, 2, None));
+        assert_eq!(parsed.measurements[0].median(), Duration::from_nanos(10));
+    }
+
+    #[test]
+    fn regressions_per_year() {
+        let baseline = Baseline::new(vec![measurement(None, 10, 3)]);
+        let mut current = measurement(None, 30, 5);
+        current.year = 2021;
+        assert!(baseline.compare(&[current], 0.2).is_empty());
+    }
+
+    #[test]
+    fn regressions() {
+        let baseline = Baseline::new(vec![
+            measurement(None, 10, 3),
+            measurement(Some("iterative"), 20, 4),
+        ]);
+        let current = vec![
+            measurement(None, 11, 3),
+            measurement(Some("iterative"), 30, 5),
+        ];
+        let regressions = baseline.compare(&current, 0.2);
+        assert_eq!(regressions.len(), 2);
+        assert_eq!(
+            regressions[0].to_string(),
+            "Day 13 - Part 2 - iterative: median time 30ms, baseline 20ms (+50.0%)"
+        );
+        assert_eq!(
+            regressions[1].to_string(),
+            "Day 13 - Part 2 - iterative: 5 allocations, baseline 4"
+        );
+    }
+}
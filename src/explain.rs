@@ -0,0 +1,64 @@
+use std::fmt::Write;
+
+use crate::runner::Answer;
+
+/// How a solution arrived at its answer: the steps of the derivation,
+/// and the lines of the puzzle input which were involved.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Explanation {
+    pub answer: Answer,
+    /// The derivation, one step per entry, like `1721 + 299 = 2020`.
+    pub steps: Vec<String>,
+    /// The (0-based) numbers of the input lines which produced the answer, in ascending order,
+    /// each with its role, like `term`.
+    pub lines: Vec<(usize, String)>,
+}
+
+impl Explanation {
+    /// Formats the explanation for display, quoting the involved lines of `input`
+    /// with their (1-based) line numbers.
+    pub fn render(&self, input: &str) -> String {
+        let mut out = String::new();
+        writeln!(out, "Answer: {}", self.answer).unwrap();
+        for step in &self.steps {
+            writeln!(out, "  {}", step).unwrap();
+        }
+        if !self.lines.is_empty() {
+            writeln!(out, "Input lines:").unwrap();
+            let input_lines = input.lines().collect::<Vec<_>>();
+            let width = (self.lines.last().unwrap().0 + 1).to_string().len();
+            for (line, role) in &self.lines {
+                let text = input_lines.get(*line).map_or("", |text| text.trim_end());
+                writeln!(
+                    out,
+                    "  {:>width$}: {}  ({})",
+                    line + 1,
+                    text,
+                    role,
+                    width = width
+                )
+                .unwrap();
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rendering() {
+        let explanation = Explanation {
+            answer: Answer::from(514579),
+            steps: vec!["1721 + 299 = 2020".to_owned()],
+            lines: vec![(0, "term".to_owned()), (9, "term".to_owned())],
+        };
+        let input = "1721\n2\n3\n4\n5\n6\n7\n8\n9\n299\n";
+        assert_eq!(
+            explanation.render(input),
+            "Answer: 514579\n  1721 + 299 = 2020\nInput lines:\n   1: 1721  (term)\n  10: 299  (term)\n"
+        );
+    }
+}
@@ -1,7 +1,711 @@
 extern crate advent_of_code_2020;
-extern crate aoc_runner;
-extern crate aoc_runner_derive;
 
-use aoc_runner_derive::aoc_main;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::process;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
-aoc_main! { lib = advent_of_code_2020 }
+use advent_of_code_2020::answers::{check, Answers};
+use advent_of_code_2020::bench::{measure, Baseline, CountingAllocator};
+use advent_of_code_2020::parallel::{run_parallel, Job, Summary};
+use advent_of_code_2020::report::{title, Format, Reporter};
+use advent_of_code_2020::runner::{find_solutions, years, InputMode, Solution};
+use advent_of_code_2020::util::{
+    normalize, read_trace, Frames, ImageFormat, Palette, Recorder, RenderOptions, TraceFilter,
+};
+use advent_of_code_2020::year2020;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "\
+Usage: advent-of-code-2020 [OPTIONS]
+
+Options:
+    -y, --year <YEAR>      Run the solutions for the given year (default: the latest year)
+    -d, --day <DAY>        Only run the solutions for the given day
+    -p, --part <PART>      Only run the solutions for the given part
+    -s, --solver <NAME>    Only run the alternative solution with the given name
+    -i, --input <PATH>     Read the puzzle input from PATH, or from stdin if PATH is `-`
+                           (default: input/YEAR/dayN.txt)
+        --strict           Report a byte order mark, `\\r\\n` line endings, trailing whitespace
+                           or trailing blank lines in the input instead of ignoring them
+    -f, --format <FORMAT>  Output format: `text`, `json` (one object per line) or `csv`
+                           (default: text)
+    -c, --check            Check the answers against the expected answers,
+                           and against other solvers for the same day and part
+    -a, --answers <PATH>   Read the expected answers from PATH
+                           (default: input/YEAR/answers.toml)
+    -P, --parallel         Run the solutions concurrently on a thread pool,
+                           and print a summary at the end
+    -j, --threads <N>      Number of threads for --parallel (default: the number of CPUs)
+        --timeout <SECONDS>
+                           Report a solution as timed out if it takes longer than SECONDS
+                           with --parallel (default: no timeout)
+    -b, --bench            Run every solution repeatedly, and report the min/median/max times
+                           and the allocations per run
+        --runs <N>         Number of runs per solution for --bench (default: 10)
+        --baseline <PATH>  Compare the --bench results against a saved baseline,
+                           and report solutions which got slower or allocate more
+        --save-baseline <PATH>
+                           Save the --bench results as a baseline to PATH
+        --tolerance <PERCENT>
+                           How much slower than the baseline a solution may get (default: 20)
+    -g, --generate <SEED>  Print a random input for --day, generated from SEED, instead of
+                           running the solutions
+        --size <N>         Number of lines or items in the generated input (default: 100)
+    -r, --render <DIR>     Write the simulation of --day as numbered PPM images to DIR,
+                           instead of running the solutions (days 11, 17, 20 and 24)
+        --scale <N>        Width and height of every cell in pixels for --render (default: 4)
+        --palette <COLORS> Comma-separated hex colors for --render, like `000000,ffffff`,
+                           instead of the day's own colors
+        --pgm              Write grayscale PGM images instead of PPM with --render
+    -t, --trace <PATH>     Write the events of the simulation of --day and --part to a trace
+                           file at PATH, or to stdout if PATH is `-`, instead of running the
+                           solutions (days 8, 11, 12, 14, 22, 23 and 24)
+        --replay <PATH>    Print the events of a trace file, instead of running the solutions
+        --kind <KIND>      Only replay events of the given kind (can be repeated)
+        --where <FIELD=VALUE>
+                           Only replay events with the given field value (can be repeated)
+        --from <STEP>      Only replay events from the given step number on
+        --to <STEP>        Only replay events up to the given step number
+        --step             Wait for Enter after every replayed event (`q` to quit,
+                           `c` to continue without waiting)
+    -e, --explain          Show how --day and --part arrive at the answer, with the input lines
+                           which produced it (days 1 and 9)
+    -V, --validate         Check the input of --day, or of all days which support it,
+                           for problems which make the solutions fail (days 5, 8, 9, 12,
+                           16, 18, 19, 20, 23 and 25), instead of running the solutions
+        --policy <PATH>    Check the passwords of day 2 against the rules in a policy file,
+                           and report the broken rule of every invalid password
+    -h, --help             Print this help message";
+
+#[derive(Debug, Default)]
+struct Options {
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<u8>,
+    solver: Option<String>,
+    input: Option<String>,
+    strict: bool,
+    format: Option<Format>,
+    check: bool,
+    answers: Option<String>,
+    parallel: bool,
+    threads: Option<usize>,
+    timeout: Option<Duration>,
+    bench: bool,
+    runs: Option<usize>,
+    baseline: Option<String>,
+    save_baseline: Option<String>,
+    tolerance: Option<f64>,
+    generate: Option<u64>,
+    size: Option<usize>,
+    render: Option<String>,
+    scale: Option<usize>,
+    palette: Option<Palette>,
+    pgm: bool,
+    trace: Option<String>,
+    replay: Option<String>,
+    kinds: Vec<String>,
+    fields: Vec<(String, String)>,
+    from: Option<u64>,
+    to: Option<u64>,
+    step: bool,
+    validate: bool,
+    explain: bool,
+    policy: Option<String>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options::default();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {}", name))
+            };
+            match arg.as_str() {
+                "-y" | "--year" => {
+                    let year = value(&arg)?;
+                    options.year = Some(year.parse().map_err(|_| "invalid year")?);
+                }
+                "-d" | "--day" => {
+                    let day = value(&arg)?;
+                    options.day = Some(parse_number(&day, 1..=25).ok_or("invalid day")?);
+                }
+                "-p" | "--part" => {
+                    let part = value(&arg)?;
+                    options.part = Some(parse_number(&part, 1..=2).ok_or("invalid part")?);
+                }
+                "-s" | "--solver" => options.solver = Some(value(&arg)?),
+                "-i" | "--input" => options.input = Some(value(&arg)?),
+                "--strict" => options.strict = true,
+                "-f" | "--format" => options.format = Some(value(&arg)?.parse()?),
+                "-c" | "--check" => options.check = true,
+                "-a" | "--answers" => options.answers = Some(value(&arg)?),
+                "-P" | "--parallel" => options.parallel = true,
+                "-j" | "--threads" => {
+                    let threads = value(&arg)?;
+                    options.threads = Some(
+                        threads
+                            .parse()
+                            .ok()
+                            .filter(|&n| n > 0)
+                            .ok_or("invalid threads")?,
+                    );
+                }
+                "--timeout" => {
+                    let timeout = value(&arg)?;
+                    options.timeout = Some(
+                        timeout
+                            .parse()
+                            .ok()
+                            .filter(|&s: &f64| s > 0.0 && s.is_finite())
+                            .map(Duration::from_secs_f64)
+                            .ok_or("invalid timeout")?,
+                    );
+                }
+                "-b" | "--bench" => options.bench = true,
+                "--runs" => {
+                    let runs = value(&arg)?;
+                    options.runs =
+                        Some(runs.parse().ok().filter(|&n| n > 0).ok_or("invalid runs")?);
+                }
+                "--baseline" => options.baseline = Some(value(&arg)?),
+                "--save-baseline" => options.save_baseline = Some(value(&arg)?),
+                "--tolerance" => {
+                    let tolerance = value(&arg)?;
+                    options.tolerance = Some(
+                        tolerance
+                            .parse()
+                            .ok()
+                            .filter(|&p: &f64| p >= 0.0)
+                            .ok_or("invalid tolerance")?,
+                    );
+                }
+                "-g" | "--generate" => {
+                    let seed = value(&arg)?;
+                    options.generate = Some(seed.parse().map_err(|_| "invalid seed")?);
+                }
+                "--size" => {
+                    let size = value(&arg)?;
+                    options.size =
+                        Some(size.parse().ok().filter(|&n| n > 0).ok_or("invalid size")?);
+                }
+                "-r" | "--render" => options.render = Some(value(&arg)?),
+                "--scale" => {
+                    let scale = value(&arg)?;
+                    options.scale = Some(
+                        scale
+                            .parse()
+                            .ok()
+                            .filter(|&n| n > 0)
+                            .ok_or("invalid scale")?,
+                    );
+                }
+                "--palette" => {
+                    let palette = value(&arg)?;
+                    options.palette = Some(palette.parse().map_err(|e| format!("{}", e))?);
+                }
+                "--pgm" => options.pgm = true,
+                "-t" | "--trace" => options.trace = Some(value(&arg)?),
+                "--replay" => options.replay = Some(value(&arg)?),
+                "--kind" => options.kinds.push(value(&arg)?),
+                "--where" => {
+                    let condition = value(&arg)?;
+                    let (name, value) = condition
+                        .split_once('=')
+                        .ok_or("invalid condition, expected FIELD=VALUE")?;
+                    options.fields.push((name.to_owned(), value.to_owned()));
+                }
+                "--from" => {
+                    let step = value(&arg)?;
+                    options.from = Some(step.parse().map_err(|_| "invalid step")?);
+                }
+                "--to" => {
+                    let step = value(&arg)?;
+                    options.to = Some(step.parse().map_err(|_| "invalid step")?);
+                }
+                "--step" => options.step = true,
+                "-V" | "--validate" => options.validate = true,
+                "-e" | "--explain" => options.explain = true,
+                "--policy" => options.policy = Some(value(&arg)?),
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    process::exit(0);
+                }
+                _ => return Err(format!("unknown argument: {}", arg)),
+            }
+        }
+        if options.input.is_some() && options.day.is_none() {
+            return Err("--input requires --day".to_owned());
+        }
+        let bench_only = options.runs.is_some()
+            || options.baseline.is_some()
+            || options.save_baseline.is_some()
+            || options.tolerance.is_some();
+        if bench_only && !options.bench {
+            return Err(
+                "--runs, --baseline, --save-baseline and --tolerance require --bench".to_owned(),
+            );
+        }
+        if options.generate.is_some() && options.day.is_none() {
+            return Err("--generate requires --day".to_owned());
+        }
+        if options.size.is_some() && options.generate.is_none() {
+            return Err("--size requires --generate".to_owned());
+        }
+        if options.render.is_some() && options.day.is_none() {
+            return Err("--render requires --day".to_owned());
+        }
+        if (options.scale.is_some() || options.palette.is_some() || options.pgm)
+            && options.render.is_none()
+        {
+            return Err("--scale, --palette and --pgm require --render".to_owned());
+        }
+        if options.trace.is_some() && (options.day.is_none() || options.part.is_none()) {
+            return Err("--trace requires --day and --part".to_owned());
+        }
+        if options.explain && (options.day.is_none() || options.part.is_none()) {
+            return Err("--explain requires --day and --part".to_owned());
+        }
+        if options.validate && options.part.is_some() {
+            return Err("--validate and --part cannot be combined".to_owned());
+        }
+        if options.policy.is_some() && options.day != Some(2) {
+            return Err("--policy requires --day 2".to_owned());
+        }
+        let replay_only = !options.kinds.is_empty()
+            || !options.fields.is_empty()
+            || options.from.is_some()
+            || options.to.is_some()
+            || options.step;
+        if replay_only && options.replay.is_none() {
+            return Err("--kind, --where, --from, --to and --step require --replay".to_owned());
+        }
+        if (options.threads.is_some() || options.timeout.is_some()) && !options.parallel {
+            return Err("--threads and --timeout require --parallel".to_owned());
+        }
+        if options.bench && options.parallel {
+            return Err("--bench and --parallel cannot be combined".to_owned());
+        }
+        if options.bench && options.check {
+            return Err("--bench and --check cannot be combined".to_owned());
+        }
+        Ok(options)
+    }
+}
+
+fn parse_number(s: &str, range: std::ops::RangeInclusive<u8>) -> Option<u8> {
+    s.parse().ok().filter(|x| range.contains(x))
+}
+
+fn read_input(path: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut input = String::new();
+    if path == "-" {
+        std::io::stdin().read_to_string(&mut input)?;
+    } else {
+        input = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Ok(input)
+}
+
+/// Calls `f` for every solution with its puzzle input, reading every input only once.
+fn with_inputs<F>(options: &Options, solutions: &[&'static Solution], mut f: F)
+where
+    F: FnMut(&'static Solution, Result<&str, Box<dyn Error + Send + Sync>>),
+{
+    let mut input_day = None;
+    let mut input = Ok(String::new());
+    for &solution in solutions {
+        if input_day != Some(solution.day) {
+            input_day = Some(solution.day);
+            input = match &options.input {
+                Some(path) => read_input(path),
+                None => read_input(&format!("input/{}/day{}.txt", solution.year, solution.day)),
+            };
+        }
+        match &input {
+            Ok(input) => f(solution, Ok(input)),
+            Err(e) => f(solution, Err(e.to_string().into())),
+        }
+    }
+}
+
+fn bench(options: &Options, year: u16, solutions: &[&'static Solution], mode: InputMode) {
+    let baseline = options.baseline.as_ref().map(|path| {
+        Baseline::load(path).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        })
+    });
+
+    println!("Benchmarking Advent of code {}", year);
+    let runs = options.runs.unwrap_or(10);
+    let mut measurements = vec![];
+    let mut failed = false;
+    with_inputs(options, solutions, |solution, input| {
+        match input.and_then(|input| measure(solution, input, runs, mode)) {
+            Ok(measurement) => {
+                println!("{}\n", measurement);
+                measurements.push(measurement);
+            }
+            Err(e) => {
+                eprintln!("{}: FAILED\n\t{}\n", title(solution), e);
+                failed = true;
+            }
+        }
+    });
+
+    if let Some(path) = &options.save_baseline {
+        let saved = Baseline::new(measurements.clone());
+        saved.save(path).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        });
+    }
+    if let Some(baseline) = baseline {
+        let tolerance = options.tolerance.unwrap_or(20.0) / 100.0;
+        let regressions = baseline.compare(&measurements, tolerance);
+        for regression in &regressions {
+            eprintln!("REGRESSION {}", regression);
+        }
+        if !regressions.is_empty() {
+            eprintln!(
+                "{} regressions found in {} solutions",
+                regressions.len(),
+                measurements.len()
+            );
+            process::exit(1);
+        }
+        eprintln!("No regressions in {} solutions", measurements.len());
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn render(options: &Options, year: u16, day: u8, dir: &str) -> Result<(), Box<dyn Error>> {
+    let renderer = Some(year)
+        .filter(|&year| year == year2020::YEAR)
+        .and_then(|_| year2020::renderer(day))
+        .ok_or_else(|| format!("{} day {} cannot be rendered", year, day))?;
+    let input = match &options.input {
+        Some(path) => read_input(path),
+        None => read_input(&format!("input/{}/day{}.txt", year, day)),
+    }
+    .map_err(|e| e.to_string())?;
+    let render_options = RenderOptions {
+        scale: options.scale.unwrap_or(4),
+        palette: options.palette.clone(),
+        format: if options.pgm {
+            ImageFormat::Pgm
+        } else {
+            ImageFormat::Ppm
+        },
+    };
+    let mut frames = Frames::new(dir, render_options).map_err(|e| format!("{}: {}", dir, e))?;
+    renderer(&input, &mut frames)?;
+    eprintln!("Wrote {} frames to {}", frames.len(), dir);
+    Ok(())
+}
+
+fn trace(
+    options: &Options,
+    year: u16,
+    day: u8,
+    part: u8,
+    path: &str,
+) -> Result<(), Box<dyn Error>> {
+    let tracer = Some(year)
+        .filter(|&year| year == year2020::YEAR)
+        .and_then(|_| year2020::tracer(day))
+        .ok_or_else(|| format!("{} day {} cannot be traced", year, day))?;
+    let input = match &options.input {
+        Some(path) => read_input(path),
+        None => read_input(&format!("input/{}/day{}.txt", year, day)),
+    }
+    .map_err(|e| e.to_string())?;
+    let out: Box<dyn Write> = if path == "-" {
+        Box::new(io::stdout())
+    } else {
+        Box::new(File::create(path).map_err(|e| format!("{}: {}", path, e))?)
+    };
+    let mut recorder = Recorder::new(Box::new(BufWriter::new(out)) as Box<dyn Write>);
+    recorder.comment(&format!("{} day {} part {}", year, day, part));
+    let answer = tracer(&input, part, &mut recorder)?;
+    recorder.comment(&format!("answer: {}", answer));
+    let steps = recorder.steps();
+    recorder.finish().map_err(|e| format!("{}: {}", path, e))?;
+    eprintln!("Answer: {}, traced {} events", answer, steps);
+    Ok(())
+}
+
+fn replay(options: &Options, path: &str) -> Result<(), Box<dyn Error>> {
+    let input = read_input(path).map_err(|e| e.to_string())?;
+    let events = read_trace(&input).map_err(|e| format!("{}: {}", path, e))?;
+    let filter = TraceFilter {
+        kinds: options.kinds.clone(),
+        fields: options.fields.clone(),
+        steps: match (options.from, options.to) {
+            (None, None) => None,
+            (from, to) => Some(from.unwrap_or(0)..=to.unwrap_or(u64::MAX)),
+        },
+    };
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let stdin = io::stdin();
+    let mut commands = stdin.lock().lines();
+    let mut stepping = options.step;
+    for event in events.iter().filter(|event| filter.matches(event)) {
+        writeln!(out, "{}", event)?;
+        if stepping {
+            out.flush()?;
+            match commands.next().transpose()?.as_deref().map(str::trim) {
+                None | Some("q") => break,
+                Some("c") => stepping = false,
+                Some(_) => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+fn explain(options: &Options, year: u16, day: u8, part: u8) -> Result<(), Box<dyn Error>> {
+    let explainer = Some(year)
+        .filter(|&year| year == year2020::YEAR)
+        .and_then(|_| year2020::explainer(day))
+        .ok_or_else(|| format!("{} day {} has no explanation", year, day))?;
+    let input = match &options.input {
+        Some(path) => read_input(path),
+        None => read_input(&format!("input/{}/day{}.txt", year, day)),
+    }
+    .map_err(|e| e.to_string())?;
+    let explanation = explainer(&input, part)?;
+    print!("{}", explanation.render(&normalize(&input)));
+    Ok(())
+}
+
+fn check_policy(options: &Options, year: u16, path: &str) -> Result<(), Box<dyn Error>> {
+    if year != year2020::YEAR {
+        return Err(format!("no password policies for {}", year).into());
+    }
+    let rules = read_input(path).map_err(|e| e.to_string())?;
+    let rules =
+        year2020::day2::parse_policy_file(&rules).map_err(|e| format!("{}: {}", path, e))?;
+    let input = match &options.input {
+        Some(path) => read_input(path),
+        None => read_input(&format!("input/{}/day2.txt", year)),
+    }
+    .map_err(|e| e.to_string())?;
+    let passwords = year2020::day2::input_generator(&input)?;
+    let results = year2020::day2::check_passwords(&rules, &passwords);
+    for (line, ((_, password), result)) in passwords.iter().zip(&results).enumerate() {
+        if let Err(failure) = result {
+            println!("line {}: {}: {}", line + 1, password, failure);
+        }
+    }
+    let valid = results.iter().filter(|result| result.is_ok()).count();
+    println!("{} of {} passwords are valid", valid, results.len());
+    Ok(())
+}
+
+/// Validates the inputs, and returns true if they are all valid.
+fn validate(options: &Options, year: u16) -> Result<bool, Box<dyn Error>> {
+    if year != year2020::YEAR {
+        return Err(format!("no input validation for {}", year).into());
+    }
+    let days = match options.day {
+        Some(day) if year2020::validator(day).is_none() => {
+            return Err(format!("{} day {} has no input validation", year, day).into());
+        }
+        Some(day) => vec![day],
+        None => (1..=25)
+            .filter(|&day| year2020::validator(day).is_some())
+            .collect(),
+    };
+    let mut valid = true;
+    for day in days {
+        let validator = year2020::validator(day).unwrap();
+        let input = match &options.input {
+            Some(path) => read_input(path),
+            None => read_input(&format!("input/{}/day{}.txt", year, day)),
+        }
+        .map_err(|e| e.to_string())?;
+        let violations = validator(&input).unwrap_or_else(|e| vec![e.to_string()]);
+        if violations.is_empty() {
+            println!("{} day {}: ok", year, day);
+        } else {
+            valid = false;
+            println!("{} day {}: {} problems", year, day, violations.len());
+            for violation in violations {
+                println!("\t{}", violation);
+            }
+        }
+    }
+    Ok(valid)
+}
+
+fn main() {
+    let options = Options::parse(std::env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("error: {}\n\n{}", e, USAGE);
+        process::exit(2);
+    });
+
+    let year = options
+        .year
+        .unwrap_or_else(|| years().last().expect("no solutions registered"));
+
+    if let (Some(seed), Some(day)) = (options.generate, options.day) {
+        if year != year2020::YEAR {
+            eprintln!("error: no input generators for {}", year);
+            process::exit(2);
+        }
+        let size = options.size.unwrap_or(100);
+        let input = year2020::gen::random_input(day, seed, size).expect("day out of range");
+        println!("{}", input);
+        return;
+    }
+
+    if let (true, Some(day), Some(part)) = (options.explain, options.day, options.part) {
+        explain(&options, year, day, part).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        });
+        return;
+    }
+
+    if let Some(path) = &options.policy {
+        check_policy(&options, year, path).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        });
+        return;
+    }
+
+    if options.validate {
+        match validate(&options, year) {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                process::exit(2);
+            }
+        }
+    }
+
+    if let Some(path) = &options.replay {
+        replay(&options, path).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        });
+        return;
+    }
+
+    if let (Some(path), Some(day), Some(part)) = (&options.trace, options.day, options.part) {
+        trace(&options, year, day, part, path).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        });
+        return;
+    }
+
+    if let (Some(dir), Some(day)) = (&options.render, options.day) {
+        render(&options, year, day, dir).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        });
+        return;
+    }
+
+    let answers = if options.check {
+        let path = options.answers.clone();
+        let path = path.unwrap_or_else(|| format!("input/{}/answers.toml", year));
+        Some(Answers::load(&path).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        }))
+    } else {
+        None
+    };
+
+    let solutions = find_solutions(
+        Some(year),
+        options.day,
+        options.part,
+        options.solver.as_deref(),
+    )
+    .collect::<Vec<_>>();
+    if solutions.is_empty() {
+        eprintln!("error: no matching solutions found");
+        process::exit(2);
+    }
+
+    let mode = if options.strict {
+        InputMode::Strict
+    } else {
+        InputMode::Lenient
+    };
+    if options.bench {
+        bench(&options, year, &solutions, mode);
+        return;
+    }
+
+    let stdout = std::io::stdout();
+    let mut reporter = Reporter::new(options.format.unwrap_or(Format::Text), stdout.lock());
+    reporter.header(year).expect("failed to write output");
+
+    let mut results = vec![];
+    if options.parallel {
+        let start = Instant::now();
+        let mut jobs: Vec<Job> = vec![];
+        with_inputs(&options, &solutions, |solution, input| {
+            let input = input.map(Arc::<str>::from);
+            jobs.push(Box::new(move || solution.run_with(&input?, mode)));
+        });
+        let threads = options
+            .threads
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+        let mut summary = Summary::default();
+        run_parallel(jobs, threads, options.timeout, |index, result| {
+            let solution = solutions[index];
+            reporter
+                .record(solution, &result)
+                .expect("failed to write output");
+            summary.add(&result);
+            results.push((solution, result));
+        });
+        summary.elapsed = start.elapsed();
+        eprintln!("{}", summary);
+    } else {
+        with_inputs(&options, &solutions, |solution, input| {
+            let result = input.and_then(|input| solution.run_with(input, mode));
+            reporter
+                .record(solution, &result)
+                .expect("failed to write output");
+            results.push((solution, result));
+        });
+    }
+
+    if let Some(answers) = answers {
+        let failures = check(&answers, &results);
+        for failure in &failures {
+            eprintln!("MISMATCH {}", failure);
+        }
+        if !failures.is_empty() {
+            eprintln!(
+                "{} problems found in {} solutions",
+                failures.len(),
+                results.len()
+            );
+            process::exit(1);
+        }
+        eprintln!("All {} solutions match the expected answers", results.len());
+    } else if results.iter().any(|(_, result)| result.is_err()) {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::runner::{panic_message, RunResult};
+
+/// A solution to run on the pool, usually a [`Solution::run_with`](crate::Solution::run_with)
+/// call with its input.
+pub type Job = Box<dyn FnOnce() -> RunResult + Send>;
+
+/// The error of a job which did not finish within its time budget.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Timeout(pub Duration);
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timed out after {:?}", self.0)
+    }
+}
+
+impl Error for Timeout {}
+
+/// The error of a job which panicked.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Panic(pub String);
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "panicked: {}", self.0)
+    }
+}
+
+impl Error for Panic {}
+
+enum Event {
+    /// A job was started. Its flag is set once the job is over: either by the worker when the job
+    /// finished, or by [`run_parallel`] when the job timed out.
+    Started(usize, Instant, Arc<AtomicBool>),
+    Finished(usize, RunResult),
+}
+
+type Queue = Arc<Mutex<VecDeque<(usize, Job)>>>;
+
+fn spawn_worker(queue: Queue, events: Sender<Event>) {
+    thread::spawn(move || loop {
+        let next = queue.lock().unwrap().pop_front();
+        let (index, job) = match next {
+            Some(next) => next,
+            None => return,
+        };
+        let over = Arc::new(AtomicBool::new(false));
+        if events
+            .send(Event::Started(index, Instant::now(), Arc::clone(&over)))
+            .is_err()
+        {
+            return;
+        }
+        let result = panic::catch_unwind(AssertUnwindSafe(job))
+            .unwrap_or_else(|payload| Err(Box::new(Panic(panic_message(payload)))));
+        // A worker whose job timed out has been replaced, so it must not take another job
+        if over.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        if events.send(Event::Finished(index, result)).is_err() {
+            return;
+        }
+    });
+}
+
+/// Runs the jobs concurrently on `threads` worker threads, and calls `f` with the index and
+/// the result of every job, in the order of the jobs.
+///
+/// A job which runs longer than `timeout` is reported as a [`Timeout`]. Its thread cannot be
+/// stopped, so it is abandoned and replaced by a new worker. The abandoned thread exits when its
+/// job finishes, so no more than `threads` jobs run at a time. A job which panics is reported as
+/// a [`Panic`].
+pub fn run_parallel<F>(jobs: Vec<Job>, threads: usize, timeout: Option<Duration>, mut f: F)
+where
+    F: FnMut(usize, RunResult),
+{
+    let count = jobs.len();
+    let queue = Arc::new(Mutex::new(jobs.into_iter().enumerate().collect()));
+    let (sender, events) = mpsc::channel();
+    for _ in 0..threads.clamp(1, count.max(1)) {
+        spawn_worker(Arc::clone(&queue), sender.clone());
+    }
+
+    let mut results = (0..count).map(|_| None).collect::<Vec<_>>();
+    let mut running = HashMap::new();
+    let mut done = 0;
+    let mut reported = 0;
+    while done < count {
+        let deadline = timeout.and_then(|timeout| {
+            running
+                .values()
+                .map(|&(start, _): &(Instant, _)| start + timeout)
+                .min()
+        });
+        let event = match deadline {
+            Some(deadline) => {
+                events.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => events.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match event {
+            Ok(Event::Started(index, start, over)) => {
+                running.insert(index, (start, over));
+            }
+            Ok(Event::Finished(index, result)) => {
+                running.remove(&index);
+                results[index] = Some(result);
+                done += 1;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let timeout = timeout.unwrap();
+                let now = Instant::now();
+                let expired = running
+                    .iter()
+                    .filter(|&(_, &(start, _))| now >= start + timeout)
+                    .map(|(&index, _)| index)
+                    .collect::<Vec<_>>();
+                for index in expired {
+                    // If the job just finished, wait for its result instead
+                    if running[&index].1.swap(true, Ordering::SeqCst) {
+                        continue;
+                    }
+                    running.remove(&index);
+                    results[index] = Some(Err(Box::new(Timeout(timeout)) as _));
+                    done += 1;
+                    spawn_worker(Arc::clone(&queue), sender.clone());
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => unreachable!("workers stopped early"),
+        }
+        while let Some(result) = results.get_mut(reported).and_then(Option::take) {
+            f(reported, result);
+            reported += 1;
+        }
+    }
+}
+
+/// Counts the results of running solutions, for a summary at the end of a run.
+#[derive(Debug, Clone, Default)]
+pub struct Summary {
+    pub solved: usize,
+    pub failed: usize,
+    pub timed_out: usize,
+    /// The sum of the generator and solver times of the solved solutions.
+    pub total_time: Duration,
+    /// The time from the start of the run until the last result.
+    pub elapsed: Duration,
+}
+
+impl Summary {
+    pub fn add(&mut self, result: &RunResult) {
+        match result {
+            Ok(output) => {
+                self.solved += 1;
+                self.total_time += output.generator_time + output.solver_time;
+            }
+            Err(e) if e.is::<Timeout>() => self.timed_out += 1,
+            Err(_) => self.failed += 1,
+        }
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} solved, {} failed, {} timed out in {:?} (total time of the solved solutions: {:?})",
+            self.solved, self.failed, self.timed_out, self.elapsed, self.total_time
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{Answer, Output};
+    use std::sync::atomic::AtomicUsize;
+
+    fn job(answer: i64, sleep: Duration) -> Job {
+        Box::new(move || {
+            thread::sleep(sleep);
+            Ok(Output {
+                answer: Answer::from(answer),
+                generator_time: Duration::ZERO,
+                solver_time: sleep,
+            })
+        })
+    }
+
+    #[test]
+    fn results_in_order() {
+        let jobs = vec![
+            job(1, Duration::from_millis(50)),
+            job(2, Duration::ZERO),
+            Box::new(|| -> RunResult { panic!("oops") }),
+            job(4, Duration::from_secs(10)),
+            job(5, Duration::ZERO),
+        ];
+        let mut results = vec![];
+        let mut summary = Summary::default();
+        run_parallel(
+            jobs,
+            2,
+            Some(Duration::from_millis(200)),
+            |index, result| {
+                summary.add(&result);
+                results.push((
+                    index,
+                    result
+                        .map(|output| output.answer)
+                        .map_err(|e| e.to_string()),
+                ));
+            },
+        );
+        assert_eq!(
+            results,
+            vec![
+                (0, Ok(Answer::Integer(1))),
+                (1, Ok(Answer::Integer(2))),
+                (2, Err("panicked: oops".to_owned())),
+                (3, Err("timed out after 200ms".to_owned())),
+                (4, Ok(Answer::Integer(5))),
+            ]
+        );
+        assert_eq!(
+            (summary.solved, summary.failed, summary.timed_out),
+            (3, 1, 1)
+        );
+    }
+
+    #[test]
+    fn bounded_after_timeout() {
+        let current = Arc::new(AtomicUsize::new(0));
+        let max = Arc::new(AtomicUsize::new(0));
+        let mut jobs = vec![job(0, Duration::from_millis(300))];
+        for i in 1..20 {
+            let (current, max) = (Arc::clone(&current), Arc::clone(&max));
+            jobs.push(Box::new(move || {
+                max.fetch_max(current.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                let result = job(i, Duration::from_millis(30))();
+                current.fetch_sub(1, Ordering::SeqCst);
+                result
+            }));
+        }
+        let mut solved = 0;
+        run_parallel(jobs, 1, Some(Duration::from_millis(50)), |_, result| {
+            solved += result.is_ok() as usize;
+        });
+        assert_eq!(solved, 19);
+        assert_eq!(max.load(Ordering::SeqCst), 1);
+    }
+}
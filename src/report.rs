@@ -0,0 +1,213 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::runner::{RunResult, Solution};
+
+/// The output format for the results of running solutions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    /// Human-readable text, same as aoc-runner.
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// Comma-separated values, with a header line.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format: {}", s)),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "year,day,part,solver,answer,generator_time_ns,solver_time_ns,error";
+
+/// Writes one record per solution in the chosen format.
+pub struct Reporter<W: Write> {
+    format: Format,
+    out: W,
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(format: Format, out: W) -> Self {
+        Reporter { format, out }
+    }
+
+    pub fn header(&mut self, year: u16) -> io::Result<()> {
+        match self.format {
+            Format::Text => writeln!(self.out, "Advent of code {}", year),
+            Format::Json => Ok(()),
+            Format::Csv => writeln!(self.out, "{}", CSV_HEADER),
+        }
+    }
+
+    pub fn record(&mut self, solution: &Solution, result: &RunResult) -> io::Result<()> {
+        match self.format {
+            Format::Text => match result {
+                Ok(output) => writeln!(
+                    self.out,
+                    "{}: {}\n\tgenerator: {:?},\n\trunner: {:?}\n",
+                    title(solution),
+                    output.answer,
+                    output.generator_time,
+                    output.solver_time
+                ),
+                Err(e) => writeln!(self.out, "{}: FAILED\n\t{}\n", title(solution), e),
+            },
+            Format::Json => {
+                let mut line = format!(
+                    "{{\"year\":{},\"day\":{},\"part\":{},\"solver\":{}",
+                    solution.year,
+                    solution.day,
+                    solution.part,
+                    solution.name.map_or("null".to_owned(), json_string)
+                );
+                match result {
+                    Ok(output) => write!(
+                        line,
+                        ",\"answer\":{},\"generator_time_ns\":{},\"solver_time_ns\":{}}}",
+                        json_string(&output.answer.to_string()),
+                        output.generator_time.as_nanos(),
+                        output.solver_time.as_nanos()
+                    ),
+                    Err(e) => write!(line, ",\"error\":{}}}", json_string(&e.to_string())),
+                }
+                .unwrap();
+                writeln!(self.out, "{}", line)
+            }
+            Format::Csv => {
+                let fields = match result {
+                    Ok(output) => [
+                        output.answer.to_string(),
+                        output.generator_time.as_nanos().to_string(),
+                        output.solver_time.as_nanos().to_string(),
+                        String::new(),
+                    ],
+                    Err(e) => [String::new(), String::new(), String::new(), e.to_string()],
+                };
+                writeln!(
+                    self.out,
+                    "{},{},{},{},{}",
+                    solution.year,
+                    solution.day,
+                    solution.part,
+                    csv_field(solution.name.unwrap_or("")),
+                    fields
+                        .iter()
+                        .map(|field| csv_field(field))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )
+            }
+        }
+    }
+}
+
+/// Returns the title of a solution, as printed by aoc-runner.
+pub fn title(solution: &Solution) -> String {
+    title_of(solution.day, solution.part, solution.name)
+}
+
+/// Returns the title of the solution with the given day, part and name, see [`title`].
+pub(crate) fn title_of(day: u8, part: u8, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("Day {} - Part {} - {}", day, part, name),
+        None => format!("Day {} - Part {}", day, part),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{find_solutions, Answer, Output};
+    use std::time::Duration;
+
+    fn report(format: Format) -> String {
+        let solution = find_solutions(Some(2020), Some(1), Some(1), None)
+            .next()
+            .unwrap();
+        let result = Ok(Output {
+            answer: Answer::from(42),
+            generator_time: Duration::from_nanos(1),
+            solver_time: Duration::from_nanos(2),
+        });
+        let mut out = vec![];
+        let mut reporter = Reporter::new(format, &mut out);
+        reporter.header(2020).unwrap();
+        reporter.record(solution, &result).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn records_with_year() {
+        assert_eq!(
+            report(Format::Json),
+            "{\"year\":2020,\"day\":1,\"part\":1,\"solver\":null,\"answer\":\"42\",\
+\"generator_time_ns\":1,\"solver_time_ns\":2}\n"
+        );
+        assert_eq!(
+            report(Format::Csv),
+            format!("{}\n2020,1,1,,42,1,2,\n", CSV_HEADER)
+        );
+    }
+
+    #[test]
+    fn escape_json() {
+        assert_eq!(json_string("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+    }
+
+    #[test]
+    fn escape_csv() {
+        assert_eq!(csv_field("123"), "123");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn text_failure() {
+        let solution = find_solutions(Some(2020), Some(1), Some(1), None)
+            .next()
+            .unwrap();
+        let mut out = vec![];
+        let mut reporter = Reporter::new(Format::Text, &mut out);
+        reporter.record(solution, &Err("oops".into())).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Day 1 - Part 1: FAILED\n\toops\n\n"
+        );
+    }
+}
@@ -0,0 +1,498 @@
+use std::any::Any;
+use std::error;
+use std::fmt::{Display, Formatter};
+use std::panic;
+use std::time::{Duration, Instant};
+
+use crate::util::{check_normalized, ParseError};
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    String(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_integer!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::String(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::String(value.to_owned())
+    }
+}
+
+/// An error returned by [`solve`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    /// There is no solution for the given day and part, or no solver with the given name.
+    NotFound {
+        year: u16,
+        day: u8,
+        part: u8,
+        solver: Option<String>,
+    },
+    /// The puzzle input could not be parsed.
+    Parse(ParseError),
+    /// The puzzle input was parsed, but the solver found no answer in it,
+    /// e.g. when no two expenses sum to 2020. Contains the reason given by the solver.
+    NoAnswer(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound {
+                year,
+                day,
+                part,
+                solver: Some(solver),
+            } => write!(
+                f,
+                "no solver `{}` for {} day {} part {}",
+                solver, year, day, part
+            ),
+            Error::NotFound {
+                year,
+                day,
+                part,
+                solver: None,
+            } => write!(f, "no solution for {} day {} part {}", year, day, part),
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::NoAnswer(reason) => write!(f, "no answer: {}", reason),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::NotFound { .. } | Error::NoAnswer(_) => None,
+            Error::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// A registered solution for one part of one day of one year.
+///
+/// This mirrors the `#[aoc(dayN, partM, name)]` registrations,
+/// so that solutions can be run without going through the aoc-runner macros,
+/// which only support a single year per crate.
+#[derive(Debug)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// The name of an alternative solution, e.g. `iterative` for `#[aoc(day13, part2, iterative)]`.
+    pub name: Option<&'static str>,
+    run: fn(&str) -> Result<Output, ParseError>,
+}
+
+/// The answer of a solution, together with the time spent generating the input and solving.
+#[derive(Debug, Clone)]
+pub struct Output {
+    pub answer: Answer,
+    pub generator_time: Duration,
+    pub solver_time: Duration,
+}
+
+/// How deviations from the canonical input format are handled: a byte order mark,
+/// `\r\n` line endings, trailing whitespace or trailing blank lines.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum InputMode {
+    /// The generators fix deviations before parsing.
+    #[default]
+    Lenient,
+    /// Deviations are reported as parse errors.
+    Strict,
+}
+
+/// The result of running a solution on a puzzle input.
+pub type RunResult = Result<Output, Box<dyn error::Error + Send + Sync>>;
+
+impl Solution {
+    /// Runs the generator and the solver on the given puzzle input.
+    pub fn run(&self, input: &str) -> RunResult {
+        self.run_with(input, InputMode::Lenient)
+    }
+
+    /// Runs the generator and the solver, handling the input format as given by `mode`.
+    pub fn run_with(&self, input: &str, mode: InputMode) -> RunResult {
+        if mode == InputMode::Strict {
+            check_normalized(input).map_err(|e| e.in_day(self.day))?;
+        }
+        Ok(self.run_timed(input)?)
+    }
+
+    /// Runs the generator and the solver on the given puzzle input, and returns the answer.
+    ///
+    /// Returns [`Error::Parse`] if the input could not be parsed, and [`Error::NoAnswer`]
+    /// if the solver gives up on a well-formed input without an answer.
+    /// The solvers panic in that case, the panic is caught and its message becomes the reason.
+    pub fn solve(&self, input: &str) -> Result<Answer, Error> {
+        let output = panic::catch_unwind(|| self.run_timed(input))
+            .map_err(|payload| Error::NoAnswer(panic_message(payload)))?;
+        Ok(output?.answer)
+    }
+
+    fn run_timed(&self, input: &str) -> Result<Output, ParseError> {
+        // The generators normalize the input themselves
+        (self.run)(input)
+    }
+}
+
+/// Registers a solver of the module `crate::$year::$module`, e.g. `year2020::day1::part1`.
+macro_rules! solution {
+    ($year:ident, $day:literal, $part:literal, $module:ident :: $solver:ident) => {
+        solution!($year, $day, $part, $module::$solver, None)
+    };
+    ($year:ident, $day:literal, $part:literal, $module:ident :: $solver:ident, $name:literal) => {
+        solution!($year, $day, $part, $module::$solver, Some($name))
+    };
+    ($year:ident, $day:literal, $part:literal, $module:ident :: $solver:ident, $name:expr) => {
+        Solution {
+            year: crate::$year::YEAR,
+            day: $day,
+            part: $part,
+            name: $name,
+            run: |input| {
+                let start_time = Instant::now();
+                let generated = crate::$year::$module::input_generator(input)?;
+                let inter_time = Instant::now();
+                let answer = Answer::from(crate::$year::$module::$solver(&generated));
+                let final_time = Instant::now();
+                Ok(Output {
+                    answer,
+                    generator_time: inter_time - start_time,
+                    solver_time: final_time - inter_time,
+                })
+            },
+        }
+    };
+}
+
+/// Every `#[aoc]` registration, in order. The tests check that none are missing.
+static SOLUTIONS: &[Solution] = &[
+    solution!(year2020, 1, 1, day1::part1),
+    solution!(year2020, 1, 2, day1::part2),
+    solution!(year2020, 2, 1, day2::part1),
+    solution!(year2020, 2, 2, day2::part2),
+    solution!(year2020, 2, 2, day2::part2_chars, "chars"),
+    solution!(year2020, 3, 1, day3::part1),
+    solution!(year2020, 3, 2, day3::part2),
+    solution!(year2020, 4, 1, day4::part1),
+    solution!(year2020, 4, 2, day4::part2),
+    solution!(year2020, 5, 1, day5::part1),
+    solution!(year2020, 5, 2, day5::part2),
+    solution!(year2020, 6, 1, day6::part1),
+    solution!(year2020, 6, 2, day6::part2),
+    solution!(year2020, 7, 1, day7::part1),
+    solution!(year2020, 7, 2, day7::part2),
+    solution!(year2020, 8, 1, day8::part1),
+    solution!(year2020, 8, 2, day8::part2),
+    solution!(year2020, 9, 1, day9::part1),
+    solution!(year2020, 9, 2, day9::part2),
+    solution!(year2020, 10, 1, day10::part1),
+    solution!(year2020, 10, 2, day10::part2),
+    solution!(year2020, 11, 1, day11::part1),
+    solution!(year2020, 11, 2, day11::part2),
+    solution!(year2020, 12, 1, day12::part1),
+    solution!(year2020, 12, 2, day12::part2),
+    solution!(year2020, 13, 1, day13::part1),
+    solution!(
+        year2020,
+        13,
+        2,
+        day13::part2_crt,
+        "chinese_remainder_theorem"
+    ),
+    solution!(year2020, 13, 2, day13::part2_iterative, "iterative"),
+    solution!(year2020, 14, 1, day14::part1),
+    solution!(year2020, 14, 2, day14::part2),
+    solution!(year2020, 15, 1, day15::part1),
+    solution!(year2020, 15, 2, day15::part2),
+    solution!(year2020, 16, 1, day16::part1),
+    solution!(year2020, 16, 2, day16::part2),
+    solution!(year2020, 17, 1, day17::part1),
+    solution!(year2020, 17, 2, day17::part2),
+    solution!(year2020, 18, 1, day18::part1),
+    solution!(year2020, 18, 2, day18::part2),
+    solution!(year2020, 19, 1, day19::part1),
+    solution!(year2020, 19, 2, day19::part2),
+    solution!(year2020, 20, 1, day20::part1),
+    solution!(year2020, 20, 2, day20::part2),
+    solution!(year2020, 21, 1, day21::part1),
+    solution!(year2020, 21, 2, day21::part2),
+    solution!(year2020, 22, 1, day22::part1),
+    solution!(year2020, 22, 2, day22::part2),
+    solution!(year2020, 23, 1, day23::part1),
+    solution!(year2020, 23, 2, day23::part2),
+    solution!(year2020, 24, 1, day24::part1),
+    solution!(year2020, 24, 2, day24::part2),
+    solution!(year2020, 25, 1, day25::part1),
+];
+
+/// Returns all registered solutions, ordered by year, day and part.
+pub fn solutions() -> &'static [Solution] {
+    SOLUTIONS
+}
+
+/// Returns the solutions matching the given year, day, part and solver name.
+/// Omitted filters match any solution.
+pub fn find_solutions(
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<u8>,
+    name: Option<&str>,
+) -> impl Iterator<Item = &'static Solution> + '_ {
+    SOLUTIONS.iter().filter(move |solution| {
+        year.is_none_or(|year| solution.year == year)
+            && day.is_none_or(|day| solution.day == day)
+            && part.is_none_or(|part| solution.part == part)
+            && name.is_none_or(|name| solution.name == Some(name))
+    })
+}
+
+/// Returns the years which have at least one solution, in ascending order.
+pub fn years() -> impl Iterator<Item = u16> {
+    let mut years = SOLUTIONS
+        .iter()
+        .map(|solution| solution.year)
+        .collect::<Vec<_>>();
+    years.dedup();
+    years.into_iter()
+}
+
+/// Returns the days of the given year which have at least one solution, in ascending order.
+pub fn days(year: u16) -> impl Iterator<Item = u8> {
+    let mut days = find_solutions(Some(year), None, None, None)
+        .map(|solution| solution.day)
+        .collect::<Vec<_>>();
+    days.dedup();
+    days.into_iter()
+}
+
+/// Returns the message of a caught panic.
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_owned()
+    }
+}
+
+/// Solves the given part of the given day of 2020, using the first registered solver.
+///
+/// Fails with [`Error::NotFound`] for a day or part without a solution, with [`Error::Parse`]
+/// for a malformed input, and with [`Error::NoAnswer`] for an input without an answer,
+/// such as a list of expenses of which none sum to 2020. See [`Solution::solve`].
+pub fn solve(day: u8, part: u8, input: &str) -> Result<Answer, Error> {
+    solve_year(crate::year2020::YEAR, day, part, input)
+}
+
+/// Solves the given part of the given day of 2020, using the alternative solver
+/// with the given name. Fails like [`solve`].
+pub fn solve_with(day: u8, part: u8, solver: &str, input: &str) -> Result<Answer, Error> {
+    solve_year_with(crate::year2020::YEAR, day, part, solver, input)
+}
+
+/// Solves the given part of the given day of any year, using the first registered solver.
+/// Fails like [`solve`].
+pub fn solve_year(year: u16, day: u8, part: u8, input: &str) -> Result<Answer, Error> {
+    find_solutions(Some(year), Some(day), Some(part), None)
+        .next()
+        .ok_or(Error::NotFound {
+            year,
+            day,
+            part,
+            solver: None,
+        })?
+        .solve(input)
+}
+
+/// Solves the given part of the given day of any year, using the alternative solver
+/// with the given name. Fails like [`solve`].
+pub fn solve_year_with(
+    year: u16,
+    day: u8,
+    part: u8,
+    solver: &str,
+    input: &str,
+) -> Result<Answer, Error> {
+    find_solutions(Some(year), Some(day), Some(part), Some(solver))
+        .next()
+        .ok_or_else(|| Error::NotFound {
+            year,
+            day,
+            part,
+            solver: Some(solver.to_owned()),
+        })?
+        .solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_example() {
+        assert_eq!(
+            solve(1, 1, "1721\n979\n366\n299\n675\n1456\n"),
+            Ok(Answer::Integer(514579))
+        );
+        assert_eq!(
+            solve(21, 2, "a b (contains x)\nb (contains y)"),
+            Ok(Answer::from("a,b"))
+        );
+        assert_eq!(
+            solve_with(13, 2, "iterative", "0\n17,x,13,19"),
+            Ok(Answer::Integer(3417))
+        );
+    }
+
+    #[test]
+    fn solve_errors() {
+        assert!(matches!(solve(25, 2, ""), Err(Error::NotFound { .. })));
+        assert!(matches!(
+            solve_with(1, 1, "missing", ""),
+            Err(Error::NotFound { .. })
+        ));
+        let error = solve(1, 1, "1\nx").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 2, column 1: expected integer, found `x`"
+        );
+        assert_eq!(
+            solve(1, 1, "1\n2\n"),
+            Err(Error::NoAnswer("no 2 expenses sum to 2020".to_owned()))
+        );
+        assert!(matches!(
+            solve(8, 2, "jmp +0\njmp +0"),
+            Err(Error::NoAnswer(_))
+        ));
+    }
+
+    #[test]
+    fn years_and_days() {
+        assert_eq!(years().collect::<Vec<_>>(), vec![2020]);
+        assert_eq!(days(2020).collect::<Vec<_>>(), (1..=25).collect::<Vec<_>>());
+        assert_eq!(days(2019).count(), 0);
+        let solutions = find_solutions(Some(2020), Some(13), Some(2), None);
+        assert_eq!(solutions.count(), 2);
+        let input = "1721\n979\n366\n299\n675\n1456\n";
+        assert_eq!(solve_year(2020, 1, 1, input), solve(1, 1, input));
+        assert_eq!(
+            solve_year_with(2020, 13, 2, "iterative", "0\n17,x,13,19"),
+            Ok(Answer::Integer(3417))
+        );
+        assert!(matches!(
+            solve_year(2019, 1, 1, input),
+            Err(Error::NotFound { year: 2019, .. })
+        ));
+    }
+
+    #[test]
+    fn every_part_has_a_solution() {
+        for day in 1..=25 {
+            for part in 1..=2 {
+                let found = find_solutions(Some(2020), Some(day), Some(part), None).count();
+                if (day, part) == (25, 2) {
+                    // Day 25 has only one part
+                    assert_eq!(found, 0);
+                } else {
+                    assert!(found > 0, "no solution for day {} part {}", day, part);
+                }
+            }
+        }
+    }
+
+    /// Checks that the registered solutions are exactly the `#[aoc(dayN, partM[, name])]`
+    /// registrations in the sources of 2020.
+    #[test]
+    fn solutions_match_aoc_registrations() {
+        let mut registered = vec![];
+        for day in 1..=25 {
+            let path = format!("{}/src/year2020/day{}.rs", env!("CARGO_MANIFEST_DIR"), day);
+            let source = std::fs::read_to_string(&path).unwrap();
+            for attribute in source.lines().filter_map(|line| {
+                line.trim()
+                    .strip_prefix("#[aoc(")
+                    .and_then(|line| line.strip_suffix(")]"))
+            }) {
+                let args = attribute.split(',').map(str::trim).collect::<Vec<_>>();
+                let day = args[0].strip_prefix("day").unwrap().parse::<u8>().unwrap();
+                let part = args[1].strip_prefix("part").unwrap().parse::<u8>().unwrap();
+                registered.push((day, part, args.get(2).map(|name| name.to_string())));
+            }
+        }
+        registered.sort();
+        let mut solutions = find_solutions(Some(2020), None, None, None)
+            .map(|solution| {
+                (
+                    solution.day,
+                    solution.part,
+                    solution.name.map(str::to_owned),
+                )
+            })
+            .collect::<Vec<_>>();
+        solutions.sort();
+        assert_eq!(solutions, registered);
+    }
+
+    #[test]
+    fn input_modes() {
+        let solution = find_solutions(Some(2020), Some(22), Some(1), None)
+            .next()
+            .unwrap();
+        let input = "\u{feff}Player 1:\r\n9\r\n2\r\n\r\nPlayer 2:\r\n5\r\n8 \r\n\r\n";
+        let output = solution.run_with(input, InputMode::Lenient).unwrap();
+        let expected = solve(22, 1, "Player 1:\n9\n2\n\nPlayer 2:\n5\n8");
+        assert_eq!(Ok(output.answer), expected);
+        let error = solution.run_with(input, InputMode::Strict).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 22, line 1, column 1: expected start of input, found `\\u{feff}`"
+        );
+        let error = solution
+            .run_with(&input[3..], InputMode::Strict)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 22, line 1, column 10: expected `\\n` line ending, found `\\r\\n`"
+        );
+    }
+}
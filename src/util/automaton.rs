@@ -0,0 +1,374 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use crate::util::{Grid, ParseError, Vector2D, VectorN, DIRECTIONS_8};
+
+/// A birth/survival rule for a life-like cellular automaton.
+///
+/// Written in B/S notation, e.g. `B3/S23` for Conway's Game of Life:
+/// a dead cell with exactly 3 live neighbours becomes alive,
+/// and a live cell with 2 or 3 live neighbours stays alive.
+/// Neighbour counts of 10 or more (up to [`Rule::MAX_COUNT`]) are separated by commas,
+/// e.g. `B3,12/S2,3`. A single count of 10 or more is followed by a trailing comma,
+/// e.g. `B12,/S`, so it is not mistaken for the counts 1 and 2.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Rule {
+    /// Bit `n` is set if a dead cell with `n` live neighbours becomes alive.
+    birth: u128,
+    /// Bit `n` is set if a live cell with `n` live neighbours stays alive.
+    survival: u128,
+}
+
+impl Rule {
+    /// The largest neighbour count a rule can contain,
+    /// which is enough for the 80 neighbours of the Moore neighbourhood in 4 dimensions.
+    pub const MAX_COUNT: usize = 127;
+
+    /// Panics if a count is larger than [`Rule::MAX_COUNT`].
+    pub fn new(birth: &[usize], survival: &[usize]) -> Rule {
+        let mask = |counts: &[usize]| {
+            counts.iter().fold(0, |mask, &n| {
+                assert!(n <= Rule::MAX_COUNT, "neighbour count {} is too large", n);
+                mask | 1 << n
+            })
+        };
+        Rule {
+            birth: mask(birth),
+            survival: mask(survival),
+        }
+    }
+
+    /// Returns whether a cell is alive in the next generation.
+    pub fn next(&self, alive: bool, live_neighbours: usize) -> bool {
+        let mask = if alive { self.survival } else { self.birth };
+        live_neighbours <= Rule::MAX_COUNT && mask & (1 << live_neighbours) != 0
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_counts = |part: &str, prefix: &str| {
+            let counts = part
+                .strip_prefix(prefix)
+                .ok_or_else(|| ParseError::at(s, part, format!("`{}`", prefix)))?;
+            if counts.contains(',') {
+                let list = counts.strip_suffix(',').unwrap_or(counts);
+                return list
+                    .split(',')
+                    .map(|count| {
+                        count
+                            .parse()
+                            .ok()
+                            .filter(|&n| n <= Rule::MAX_COUNT)
+                            .ok_or_else(|| {
+                                ParseError::at(s, count, "neighbour count of at most 127")
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>();
+            }
+            counts
+                .char_indices()
+                .map(|(i, c)| {
+                    c.to_digit(10).map(|n| n as usize).ok_or_else(|| {
+                        ParseError::at(s, &counts[i..i + c.len_utf8()], "neighbour count")
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let (birth, survival) = s
+            .split_once('/')
+            .ok_or_else(|| ParseError::at(s, s, "`B.../S...`"))?;
+        Ok(Rule::new(
+            &parse_counts(birth, "B")?,
+            &parse_counts(survival, "S")?,
+        ))
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let counts = |mask: u128| {
+            let counts = (0..=Rule::MAX_COUNT)
+                .filter(|&n| mask & (1 << n) != 0)
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>();
+            match counts.len() {
+                _ if mask >> 10 == 0 => counts.join(""),
+                1 => format!("{},", counts[0]),
+                _ => counts.join(","),
+            }
+        };
+        write!(f, "B{}/S{}", counts(self.birth), counts(self.survival))
+    }
+}
+
+/// Returns the Moore neighbourhood in `D` dimensions: all cells at Chebyshev distance 1.
+pub fn moore<const D: usize>() -> impl Fn(VectorN<D>) -> Vec<VectorN<D>> {
+    let steps = VectorN::<D>::neighbours();
+    move |cell| steps.iter().map(|&step| cell + step).collect()
+}
+
+/// The steps to the 6 neighbours of a hexagon in axial coordinates:
+/// east and west are `(±1, 0)`, north-east and south-west are `(0, ±1)`.
+pub const HEX_DIRECTIONS: [Vector2D; 6] = [
+    Vector2D::new(1, 0),
+    Vector2D::new(1, -1),
+    Vector2D::new(0, -1),
+    Vector2D::new(-1, 0),
+    Vector2D::new(-1, 1),
+    Vector2D::new(0, 1),
+];
+
+/// Returns the neighbourhood of a hexagonal grid in axial coordinates, see [`HEX_DIRECTIONS`].
+pub fn hex() -> impl Fn(Vector2D) -> Vec<Vector2D> {
+    |cell| HEX_DIRECTIONS.iter().map(|&step| cell + step).collect()
+}
+
+/// Returns the Moore neighbourhood on a grid, restricted to the cells for which `is_cell`
+/// returns true.
+pub fn grid_moore<'a, T>(
+    grid: &'a Grid<T>,
+    is_cell: impl Fn(&T) -> bool + 'a,
+) -> impl Fn(Vector2D) -> Vec<Vector2D> + 'a {
+    move |pos| {
+        grid.neighbours8(pos)
+            .filter(|&neighbour| is_cell(&grid[neighbour]))
+            .collect()
+    }
+}
+
+/// Returns the line-of-sight neighbourhood on a grid: the first cell for which `is_cell`
+/// returns true in each of the 8 directions, skipping over all other positions.
+pub fn line_of_sight<'a, T>(
+    grid: &'a Grid<T>,
+    is_cell: impl Fn(&T) -> bool + 'a,
+) -> impl Fn(Vector2D) -> Vec<Vector2D> + 'a {
+    move |pos| {
+        DIRECTIONS_8
+            .iter()
+            .filter_map(|&step| grid.ray(pos, step).find(|&pos| is_cell(&grid[pos])))
+            .collect()
+    }
+}
+
+/// A life-like cellular automaton, where every cell is either dead or alive.
+///
+/// The state of the automaton is the set of live cells. The universe is either unbounded,
+/// in which case only live cells and their neighbours are considered,
+/// or a fixed set of cells given with [`Automaton::bounded`].
+pub struct Automaton<C, N> {
+    rule: Rule,
+    neighbourhood: N,
+    /// All cells with their neighbours, for a bounded universe.
+    cells: Option<Vec<(C, Vec<C>)>>,
+}
+
+/// Where a sequence of generations starts repeating.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cycle {
+    /// The first generation that is repeated later on.
+    pub start: usize,
+    /// The number of generations after which the state repeats, 1 for a stable state.
+    pub period: usize,
+}
+
+impl<C, N> Automaton<C, N>
+where
+    C: Copy + Eq + Hash,
+    N: Fn(C) -> Vec<C>,
+{
+    /// Creates an automaton in an unbounded universe.
+    /// Panics if the rule allows births with 0 live neighbours,
+    /// which would fill the unbounded universe.
+    pub fn new(rule: Rule, neighbourhood: N) -> Self {
+        assert!(!rule.next(false, 0), "{} needs a bounded universe", rule);
+        Automaton {
+            rule,
+            neighbourhood,
+            cells: None,
+        }
+    }
+
+    /// Creates an automaton in a universe which consists of the given cells only.
+    pub fn bounded(rule: Rule, neighbourhood: N, cells: impl IntoIterator<Item = C>) -> Self {
+        let cells = cells
+            .into_iter()
+            .map(|cell| (cell, neighbourhood(cell)))
+            .collect();
+        Automaton {
+            rule,
+            neighbourhood,
+            cells: Some(cells),
+        }
+    }
+
+    /// Computes the next generation.
+    pub fn step(&self, live: &HashSet<C>) -> HashSet<C> {
+        match &self.cells {
+            Some(cells) => cells
+                .iter()
+                .filter(|(cell, neighbours)| {
+                    let count = neighbours.iter().filter(|n| live.contains(n)).count();
+                    self.rule.next(live.contains(cell), count)
+                })
+                .map(|&(cell, _)| cell)
+                .collect(),
+            None => {
+                let mut counts = HashMap::<C, usize>::new();
+                for &cell in live {
+                    counts.entry(cell).or_default();
+                    for neighbour in (self.neighbourhood)(cell) {
+                        *counts.entry(neighbour).or_default() += 1;
+                    }
+                }
+                counts
+                    .into_iter()
+                    .filter(|&(cell, count)| self.rule.next(live.contains(&cell), count))
+                    .map(|(cell, _)| cell)
+                    .collect()
+            }
+        }
+    }
+
+    /// Computes the state after the given number of generations.
+    pub fn run(&self, live: &HashSet<C>, generations: usize) -> HashSet<C> {
+        let mut live = live.clone();
+        for _ in 0..generations {
+            live = self.step(&live);
+        }
+        live
+    }
+
+    /// Runs until the state does not change anymore,
+    /// and returns the final state and the number of generations until then.
+    /// Does not terminate if the automaton never stabilizes.
+    pub fn run_until_stable(&self, live: &HashSet<C>) -> (HashSet<C>, usize) {
+        let mut live = live.clone();
+        let mut generations = 0;
+        loop {
+            let next = self.step(&live);
+            if next == live {
+                return (live, generations);
+            }
+            live = next;
+            generations += 1;
+        }
+    }
+
+    /// Runs until a state repeats, and returns where the cycle starts and its period.
+    /// All states are kept in memory, so this is only suitable for short cycles.
+    pub fn find_cycle(&self, live: &HashSet<C>) -> Cycle {
+        let mut seen = HashMap::<u64, Vec<usize>>::new();
+        let mut states = vec![live.clone()];
+        loop {
+            let generation = states.len() - 1;
+            let state = &states[generation];
+            let candidates = seen.entry(fingerprint(state)).or_default();
+            if let Some(&start) = candidates.iter().find(|&&i| states[i] == *state) {
+                return Cycle {
+                    start,
+                    period: generation - start,
+                };
+            }
+            candidates.push(generation);
+            let next = self.step(state);
+            states.push(next);
+        }
+    }
+}
+
+/// Hashes a set of cells, independent of the iteration order.
+fn fingerprint<C: Hash>(cells: &HashSet<C>) -> u64 {
+    cells
+        .iter()
+        .map(|cell| {
+            let mut hasher = DefaultHasher::new();
+            cell.hash(&mut hasher);
+            hasher.finish()
+        })
+        .fold(cells.len() as u64, u64::wrapping_add)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(cells: &[(i32, i32)]) -> HashSet<Vector2D> {
+        cells.iter().map(|&(x, y)| Vector2D::new(x, y)).collect()
+    }
+
+    #[test]
+    fn parse_rule() {
+        let rule = "B3/S23".parse::<Rule>().unwrap();
+        assert_eq!(rule, Rule::new(&[3], &[2, 3]));
+        assert_eq!(rule.to_string(), "B3/S23");
+        assert!(rule.next(false, 3));
+        assert!(!rule.next(false, 2));
+        assert!(rule.next(true, 2));
+        assert!(!rule.next(true, 4));
+        let error = "B3/X23".parse::<Rule>().unwrap_err();
+        assert_eq!(error.column(), Some(4));
+
+        let rule = "B3,12/S2,3,80".parse::<Rule>().unwrap();
+        assert_eq!(rule, Rule::new(&[3, 12], &[2, 3, 80]));
+        assert_eq!(rule.to_string(), "B3,12/S2,3,80");
+        assert!(rule.next(true, 80));
+        assert!(!rule.next(true, 200));
+        let error = "B3/S2,128".parse::<Rule>().unwrap_err();
+        assert_eq!(error.column(), Some(7));
+    }
+
+    #[test]
+    fn rule_round_trip() {
+        for rule in [
+            Rule::new(&[12], &[]),
+            Rule::new(&[], &[127]),
+            Rule::new(&[3, 12], &[2]),
+            Rule::new(&[3], &[2, 3]),
+        ] {
+            assert_eq!(rule.to_string().parse::<Rule>().unwrap(), rule);
+        }
+        assert_eq!(Rule::new(&[12], &[]).to_string(), "B12,/S");
+        assert_eq!("B12/S".parse::<Rule>().unwrap(), Rule::new(&[1, 2], &[]));
+    }
+
+    #[test]
+    fn blinker() {
+        let life = Automaton::new("B3/S23".parse().unwrap(), moore::<2>());
+        let blinker = cells(&[(0, 1), (1, 1), (2, 1)]);
+        assert_eq!(life.step(&blinker), cells(&[(1, 0), (1, 1), (1, 2)]));
+        assert_eq!(life.run(&blinker, 2), blinker);
+        assert_eq!(
+            life.find_cycle(&blinker),
+            Cycle {
+                start: 0,
+                period: 2
+            }
+        );
+    }
+
+    #[test]
+    fn stable() {
+        let life = Automaton::new("B3/S23".parse().unwrap(), moore::<2>());
+        // Three cells in an L shape become a 2x2 block, which is stable
+        let (block, generations) = life.run_until_stable(&cells(&[(0, 0), (1, 0), (0, 1)]));
+        assert_eq!(block, cells(&[(0, 0), (1, 0), (0, 1), (1, 1)]));
+        assert_eq!(generations, 1);
+    }
+
+    #[test]
+    fn bounded() {
+        // Every cell flips in every generation
+        let grid = Grid::filled(2, 2, ());
+        let rule = "B012345678/S".parse().unwrap();
+        let automaton = Automaton::bounded(rule, grid_moore(&grid, |_| true), grid.positions());
+        let all = grid.positions().collect::<HashSet<_>>();
+        assert_eq!(automaton.step(&HashSet::new()), all);
+        assert_eq!(automaton.step(&all), HashSet::new());
+    }
+}
@@ -0,0 +1,241 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An error encountered while parsing a puzzle input.
+///
+/// Lines and columns are 1-based. An error is usually created close to the offending token,
+/// and then enriched with the line number and day by the enclosing generator.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    day: Option<u8>,
+    line: Option<usize>,
+    column: Option<usize>,
+    expected: String,
+    found: String,
+}
+
+impl ParseError {
+    pub fn new(expected: impl Into<String>, found: impl Into<String>) -> ParseError {
+        ParseError {
+            day: None,
+            line: None,
+            column: None,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// Creates an error for `token`, which must be a slice of `source`.
+    /// The line and column are derived from the position of the token inside the source.
+    pub fn at(source: &str, token: &str, expected: impl Into<String>) -> ParseError {
+        let mut error = ParseError::new(expected, token);
+        if let Some((line, column)) = position(source, token) {
+            error.line = Some(line);
+            error.column = Some(column);
+        }
+        error
+    }
+
+    /// Creates an error for the character starting at byte `index` of `source`.
+    pub fn at_char(source: &str, index: usize, expected: impl Into<String>) -> ParseError {
+        let len = source[index..].chars().next().map_or(0, char::len_utf8);
+        ParseError::at(source, &source[index..index + len], expected)
+    }
+
+    pub fn in_day(mut self, day: u8) -> ParseError {
+        self.day = Some(day);
+        self
+    }
+
+    pub fn with_line(mut self, line: usize) -> ParseError {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn with_column(mut self, column: usize) -> ParseError {
+        self.column = Some(column);
+        self
+    }
+
+    /// Shifts the line number by `offset` lines.
+    /// Used when the error was created relative to a part of the input, such as a single line
+    /// or a block of lines.
+    pub fn offset_lines(mut self, offset: usize) -> ParseError {
+        self.line = Some(self.line.unwrap_or(1) + offset);
+        self
+    }
+
+    /// Combines the errors of two alternatives: the error which got furthest into the input
+    /// wins, and if both failed at the same position, either of them was expected.
+    pub fn or(self, other: ParseError) -> ParseError {
+        match (self.line, self.column).cmp(&(other.line, other.column)) {
+            Ordering::Less => other,
+            Ordering::Greater => self,
+            Ordering::Equal if self.expected == other.expected => self,
+            Ordering::Equal => ParseError {
+                expected: format!("{} or {}", self.expected, other.expected),
+                ..self
+            },
+        }
+    }
+
+    pub fn day(&self) -> Option<u8> {
+        self.day
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    pub fn found(&self) -> &str {
+        &self.found
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let location = [
+            self.day.map(|day| format!("day {}", day)),
+            self.line.map(|line| format!("line {}", line)),
+            self.column.map(|column| format!("column {}", column)),
+        ]
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>();
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(", "))?;
+        }
+        match self.found.as_str() {
+            "" => write!(f, "expected {}, found end of input", self.expected),
+            "\n" => write!(f, "expected {}, found end of line", self.expected),
+            found => write!(f, "expected {}, found `{}`", self.expected, found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Returns the 1-based line and column of `token` inside `source`,
+/// or `None` if the token is not a slice of the source.
+fn position(source: &str, token: &str) -> Option<(usize, usize)> {
+    let start = source.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize).checked_sub(start)?;
+    if offset > source.len() {
+        return None;
+    }
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    Some((line, column))
+}
+
+/// Parses `token` (a slice of `source`) into a value, reporting `expected` on failure.
+pub fn parse_token<T: FromStr>(source: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(source, token, expected))
+}
+
+/// Splits `s` (a slice of `source`) around the first occurrence of `separator`.
+pub fn split_pair<'a>(
+    source: &str,
+    s: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(separator)
+        .ok_or_else(|| ParseError::at(source, s, format!("`{}`", separator)))
+}
+
+/// Strips `prefix` from `s` (a slice of `source`).
+pub fn strip_prefix<'a>(source: &str, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    s.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::at(source, s, format!("`{}`", prefix)))
+}
+
+/// Strips `suffix` from `s` (a slice of `source`).
+pub fn strip_suffix<'a>(source: &str, s: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+    s.strip_suffix(suffix)
+        .ok_or_else(|| ParseError::at(source, s, format!("`{}`", suffix)))
+}
+
+/// Parses every line of `input` with `f`, attaching the line number to any error.
+pub fn parse_lines<T, F>(input: &str, mut f: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.offset_lines(i)))
+        .collect()
+}
+
+/// Splits `input` into blocks separated by blank lines,
+/// together with the (0-based) index of the first line of each block.
+pub fn split_blocks(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.split("\n\n").scan(0, |line, block| {
+        let start = *line;
+        *line += block.lines().count() + 1;
+        Some((start, block))
+    })
+}
+
+/// Parses every block of `input` with `f`, attaching the line number to any error.
+pub fn parse_blocks<T, F>(input: &str, mut f: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    split_blocks(input)
+        .map(|(start, block)| f(block).map_err(|e| e.offset_lines(start)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_position() {
+        let source = "acc +1\nfoo -3";
+        let token = &source[7..10];
+        let error = ParseError::at(source, token, "`acc`, `jmp` or `nop`").in_day(8);
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.column(), Some(1));
+        assert_eq!(
+            error.to_string(),
+            "day 8, line 2, column 1: expected `acc`, `jmp` or `nop`, found `foo`"
+        );
+    }
+
+    #[test]
+    fn lines_are_offset() {
+        let error = parse_lines("1\n2\nx\n4", |line| {
+            parse_token::<i32>(line, line, "integer")
+        })
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected integer, found `x`"
+        );
+    }
+
+    #[test]
+    fn blocks_are_offset() {
+        let blocks = split_blocks("a\nb\n\nc\n\nd\ne\nf\n\ng")
+            .map(|(line, _)| line)
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, vec![0, 3, 5, 9]);
+    }
+}
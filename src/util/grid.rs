@@ -0,0 +1,402 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::util::{ParseError, Vector2D};
+
+/// The steps to the 4 orthogonally adjacent positions.
+pub const DIRECTIONS_4: [Vector2D; 4] = [
+    Vector2D::new(0, -1),
+    Vector2D::new(1, 0),
+    Vector2D::new(0, 1),
+    Vector2D::new(-1, 0),
+];
+
+/// The steps to the 8 adjacent positions, including diagonals.
+pub const DIRECTIONS_8: [Vector2D; 8] = [
+    Vector2D::new(-1, -1),
+    Vector2D::new(0, -1),
+    Vector2D::new(1, -1),
+    Vector2D::new(-1, 0),
+    Vector2D::new(1, 0),
+    Vector2D::new(-1, 1),
+    Vector2D::new(0, 1),
+    Vector2D::new(1, 1),
+];
+
+/// One of the 8 symmetries of a square (the dihedral group D4).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Transform {
+    Identity,
+    /// Rotate 90 degrees clockwise.
+    RotateRight,
+    Rotate180,
+    /// Rotate 90 degrees counter-clockwise.
+    RotateLeft,
+    /// Reverse the order of the rows.
+    FlipVertical,
+    /// Mirror along the main diagonal, same as `FlipVertical` followed by `RotateRight`.
+    Transpose,
+    /// Reverse the order of the columns.
+    FlipHorizontal,
+    /// Mirror along the anti-diagonal, same as `FlipVertical` followed by `RotateLeft`.
+    AntiTranspose,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::RotateRight,
+        Transform::Rotate180,
+        Transform::RotateLeft,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::FlipHorizontal,
+        Transform::AntiTranspose,
+    ];
+
+    /// Returns true if the transform swaps the width and height of a grid.
+    fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Transform::RotateRight
+                | Transform::RotateLeft
+                | Transform::Transpose
+                | Transform::AntiTranspose
+        )
+    }
+
+    /// Maps a position in the transformed grid back to the position in the original grid,
+    /// which has the given width and height.
+    fn source(self, pos: Vector2D, width: i32, height: i32) -> Vector2D {
+        let (x, y) = (pos.x(), pos.y());
+        match self {
+            Transform::Identity => Vector2D::new(x, y),
+            Transform::RotateRight => Vector2D::new(y, height - 1 - x),
+            Transform::Rotate180 => Vector2D::new(width - 1 - x, height - 1 - y),
+            Transform::RotateLeft => Vector2D::new(width - 1 - y, x),
+            Transform::FlipVertical => Vector2D::new(x, height - 1 - y),
+            Transform::Transpose => Vector2D::new(y, x),
+            Transform::FlipHorizontal => Vector2D::new(width - 1 - x, y),
+            Transform::AntiTranspose => Vector2D::new(width - 1 - y, height - 1 - x),
+        }
+    }
+}
+
+/// A dense, rectangular grid of cells, stored in row-major order.
+///
+/// Positions are `Vector2D`s with `x` going right and `y` going down, starting at `(0, 0)`
+/// in the top left corner.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "wrong number of cells");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid by calling `f` for every position.
+    pub fn from_fn<F>(width: usize, height: usize, f: F) -> Grid<T>
+    where
+        F: FnMut(Vector2D) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Vector2D::new(x as i32, y as i32)))
+            .map(f)
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    /// Parses a grid from a character map, with one line per row.
+    /// `f` converts a character into a cell, or returns `None` if the character is invalid,
+    /// in which case an error is reported with `expected`.
+    pub fn parse<F>(input: &str, expected: &str, mut f: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        for (y, line) in input.lines().enumerate() {
+            let mut chars = line.char_indices();
+            for _ in 0..width {
+                let (i, c) = chars.next().ok_or_else(|| {
+                    ParseError::at(line, &line[line.len()..], expected).offset_lines(y)
+                })?;
+                let cell =
+                    f(c).ok_or_else(|| ParseError::at_char(line, i, expected).offset_lines(y))?;
+                cells.push(cell);
+            }
+            if let Some((i, _)) = chars.next() {
+                return Err(ParseError::at(line, &line[i..], "end of row").offset_lines(y));
+            }
+            height += 1;
+        }
+        Ok(Grid::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns true if the position is inside the grid.
+    pub fn contains(&self, pos: Vector2D) -> bool {
+        (0..self.width as i32).contains(&pos.x()) && (0..self.height as i32).contains(&pos.y())
+    }
+
+    fn index_of(&self, pos: Vector2D) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.y() as usize * self.width + pos.x() as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Vector2D) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Vector2D) -> Option<&mut T> {
+        self.index_of(pos).map(move |i| &mut self.cells[i])
+    }
+
+    /// Returns the cell at the given position, as if the grid was repeated infinitely
+    /// in all directions.
+    pub fn get_wrapping(&self, pos: Vector2D) -> &T {
+        let pos = Vector2D::new(
+            pos.x().rem_euclid(self.width as i32),
+            pos.y().rem_euclid(self.height as i32),
+        );
+        &self[pos]
+    }
+
+    /// Returns all positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Vector2D> {
+        let width = self.width;
+        (0..self.height)
+            .flat_map(move |y| (0..width).map(move |x| Vector2D::new(x as i32, y as i32)))
+    }
+
+    /// Returns all cells in row-major order.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Returns all positions and cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2D, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` avoids a panic on an empty grid, which has no cells anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Returns the orthogonally adjacent positions inside the grid.
+    pub fn neighbours4(&self, pos: Vector2D) -> impl Iterator<Item = Vector2D> + '_ {
+        DIRECTIONS_4
+            .iter()
+            .map(move |&step| pos + step)
+            .filter(move |&neighbour| self.contains(neighbour))
+    }
+
+    /// Returns the adjacent positions inside the grid, including diagonals.
+    pub fn neighbours8(&self, pos: Vector2D) -> impl Iterator<Item = Vector2D> + '_ {
+        DIRECTIONS_8
+            .iter()
+            .map(move |&step| pos + step)
+            .filter(move |&neighbour| self.contains(neighbour))
+    }
+
+    /// Returns the positions inside the grid seen from `pos` in the direction of `step`,
+    /// starting with the closest one.
+    ///
+    /// Panics if `step` is zero, since the ray would never leave the grid.
+    pub fn ray(&self, pos: Vector2D, step: Vector2D) -> impl Iterator<Item = Vector2D> + '_ {
+        assert!(step != Vector2D::zero(), "ray step must not be zero");
+        let mut pos = pos;
+        std::iter::from_fn(move || {
+            pos += step;
+            Some(pos)
+        })
+        .take_while(move |&pos| self.contains(pos))
+    }
+
+    /// Creates a new grid by applying `f` to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with all cells set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Extracts the sub-grid with the given top left corner and size.
+    /// Panics if the sub-grid is not completely inside the grid.
+    pub fn sub_grid(&self, top_left: Vector2D, width: usize, height: usize) -> Grid<T> {
+        Grid::from_fn(width, height, |pos| self[top_left + pos].clone())
+    }
+
+    /// Returns a transformed copy of the grid.
+    pub fn transform(&self, transform: Transform) -> Grid<T> {
+        let (width, height) = if transform.swaps_axes() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        Grid::from_fn(width, height, |pos| {
+            self[transform.source(pos, self.width as i32, self.height as i32)].clone()
+        })
+    }
+
+    /// Returns the 8 rotations and reflections of the grid, in the order of `Transform::ALL`.
+    pub fn transforms(&self) -> impl Iterator<Item = Grid<T>> + '_ {
+        Transform::ALL
+            .iter()
+            .map(move |&transform| self.transform(transform))
+    }
+}
+
+impl<T> Index<Vector2D> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vector2D) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {:?} out of bounds", pos))
+    }
+}
+
+impl<T> IndexMut<Vector2D> for Grid<T> {
+    fn index_mut(&mut self, pos: Vector2D) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {:?} out of bounds", pos))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.rows().try_for_each(|row| {
+            row.iter().try_for_each(|cell| write!(f, "{}", cell))?;
+            writeln!(f)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Grid<char> {
+        Grid::parse(input, "any character", Some).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = parse("abc\ndef");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Vector2D::new(1, 1)], 'e');
+        assert_eq!(grid.get(Vector2D::new(3, 0)), None);
+        assert_eq!(*grid.get_wrapping(Vector2D::new(-1, 3)), 'f');
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Grid::parse("#.\n.x", "`#` or `.`", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap_err();
+        assert_eq!((error.line(), error.column()), (Some(2), Some(2)));
+        let error = Grid::parse("ab\nabc", "any character", Some).unwrap_err();
+        assert_eq!((error.line(), error.column()), (Some(2), Some(3)));
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = parse("abc\ndef");
+        let transformed = grid
+            .transforms()
+            .map(|grid| grid.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transformed,
+            vec![
+                "abc\ndef\n",
+                "da\neb\nfc\n",
+                "fed\ncba\n",
+                "cf\nbe\nad\n",
+                "def\nabc\n",
+                "ad\nbe\ncf\n",
+                "cba\nfed\n",
+                "fc\neb\nda\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = parse("abc\ndef\nghi");
+        let corner = grid.neighbours8(Vector2D::zero()).collect::<Vec<_>>();
+        assert_eq!(
+            corner,
+            vec![
+                Vector2D::new(1, 0),
+                Vector2D::new(0, 1),
+                Vector2D::new(1, 1)
+            ]
+        );
+        assert_eq!(grid.neighbours4(Vector2D::new(1, 1)).count(), 4);
+        let ray = grid
+            .ray(Vector2D::zero(), Vector2D::new(1, 1))
+            .map(|pos| grid[pos])
+            .collect::<String>();
+        assert_eq!(ray, "ei");
+    }
+
+    #[test]
+    #[should_panic(expected = "ray step must not be zero")]
+    fn ray_without_step() {
+        let grid = parse("ab");
+        grid.ray(Vector2D::zero(), Vector2D::zero()).count();
+    }
+
+    #[test]
+    fn sub_grid() {
+        let grid = parse("abc\ndef\nghi");
+        assert_eq!(
+            grid.sub_grid(Vector2D::new(1, 1), 2, 2).to_string(),
+            "ef\nhi\n"
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "beh");
+    }
+}
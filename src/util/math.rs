@@ -1,5 +1,34 @@
-#[allow(dead_code)]
-pub fn gcd(mut a: i32, mut b: i32) -> i32 {
+//! Number theory on any primitive integer type up to 64 bits.
+//!
+//! All intermediate results are computed with 128 bits, so that products of two numbers below the
+//! modulus never overflow, even for `u64` moduli.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// A primitive integer type of at most 64 bits, such as `i32`, `i64` or `u64`.
+pub trait Integer: Copy + Into<i128> + TryFrom<i128> {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {})*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+/// Converts back from an `i128`, or returns `None` if the value does not fit.
+fn narrow<T: Integer>(value: i128) -> Option<T> {
+    T::try_from(value).ok()
+}
+
+/// Returns `a * b mod modulus` for `a` and `b` in `0..modulus`.
+fn mul_mod_128(a: i128, b: i128, modulus: i128) -> i128 {
+    // Both factors are below 2^64, so the product fits in a u128 (but not always in an i128)
+    (a as u128 * b as u128 % modulus as u128) as i128
+}
+
+fn gcd_128(mut a: i128, mut b: i128) -> i128 {
     while a != 0 {
         let old_a = a;
         a = b % a;
@@ -8,22 +37,270 @@ pub fn gcd(mut a: i32, mut b: i32) -> i32 {
     b.abs()
 }
 
-#[allow(dead_code)]
-pub fn gcd_64(mut a: i64, mut b: i64) -> i64 {
-    while a != 0 {
-        let old_a = a;
-        a = b % a;
-        b = old_a;
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    narrow(gcd_128(a.into(), b.into())).expect("gcd overflows")
+}
+
+/// Returns the least common multiple, which is 0 if either number is 0.
+///
+/// Panics if the result does not fit in `T`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    let (a, b) = (a.into(), b.into());
+    let lcm = if a == 0 || b == 0 {
+        0
+    } else {
+        (a / gcd_128(a, b))
+            .checked_mul(b)
+            .expect("lcm overflows")
+            .abs()
+    };
+    narrow(lcm).expect("lcm overflows")
+}
+
+fn extended_gcd_128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
     }
-    b.abs()
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Returns `(g, x, y)` such that `g = gcd(a, b) = a * x + b * y`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_128(a.into(), b.into());
+    // |x| <= |b| / g and |y| <= |a| / g, except for gcd(i64::MIN, 0)
+    (
+        narrow(g).expect("gcd overflows"),
+        narrow(x).unwrap(),
+        narrow(y).unwrap(),
+    )
 }
 
-#[allow(dead_code)]
-pub fn lcm(a: i32, b: i32) -> i32 {
-    (a * b).abs() / gcd(a, b)
+fn mod_inverse_128(a: i128, modulus: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd_128(a, modulus);
+    if g == 1 {
+        Some(x.rem_euclid(modulus))
+    } else {
+        None
+    }
+}
+
+/// Returns `x` in `0..modulus` such that `a * x ≡ 1 (mod modulus)`,
+/// or `None` if `a` and `modulus` are not coprime.
+pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+    let modulus = modulus.into();
+    assert!(modulus > 0, "modulus must be positive");
+    narrow(mod_inverse_128(a.into(), modulus)?)
+}
+
+/// Returns `a * b mod modulus`, in `0..modulus`.
+pub fn mod_mul<T: Integer>(a: T, b: T, modulus: T) -> T {
+    let modulus = modulus.into();
+    assert!(modulus > 0, "modulus must be positive");
+    let a = a.into().rem_euclid(modulus);
+    let b = b.into().rem_euclid(modulus);
+    narrow(mul_mod_128(a, b, modulus)).unwrap()
+}
+
+fn mod_pow_128(base: i128, mut exponent: u64, modulus: i128) -> i128 {
+    let mut base = base.rem_euclid(modulus);
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod_128(result, base, modulus);
+        }
+        base = mul_mod_128(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
 }
 
-#[allow(dead_code)]
-pub fn lcm_64(a: i64, b: i64) -> i64 {
-    (a * b).abs() / gcd_64(a, b)
+/// Returns `base ^ exponent mod modulus`, in `0..modulus`, by repeated squaring.
+pub fn mod_pow<T: Integer>(base: T, exponent: u64, modulus: T) -> T {
+    let modulus = modulus.into();
+    assert!(modulus > 0, "modulus must be positive");
+    narrow(mod_pow_128(base.into(), exponent, modulus)).unwrap()
+}
+
+/// Solves a system of congruences `x ≡ remainder (mod modulus)` with the Chinese remainder theorem,
+/// generalized to moduli which are not pairwise coprime.
+///
+/// Returns the smallest non-negative solution and the least common multiple of the moduli
+/// (all solutions are congruent modulo that number), or `None` if the congruences contradict
+/// each other or the least common multiple does not fit in `T`.
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let (mut x, mut lcm) = (0i128, 1i128);
+    for &(remainder, modulus) in congruences {
+        let modulus = modulus.into();
+        assert!(modulus > 0, "modulus must be positive");
+        let remainder = remainder.into().rem_euclid(modulus);
+        let g = gcd_128(lcm, modulus);
+        let difference = remainder - x;
+        if difference % g != 0 {
+            return None;
+        }
+        // Solve x + lcm * k ≡ remainder (mod modulus) for k
+        let reduced = modulus / g;
+        let inverse = mod_inverse_128((lcm / g) % reduced, reduced).unwrap();
+        let k = mul_mod_128((difference / g).rem_euclid(reduced), inverse, reduced);
+        // With u64 moduli, the new lcm may not even fit in an i128
+        x = lcm.checked_mul(k)?.checked_add(x)?;
+        lcm = lcm.checked_mul(reduced)?;
+        narrow::<T>(lcm)?;
+        x = x.rem_euclid(lcm);
+    }
+    Some((narrow(x)?, narrow(lcm)?))
+}
+
+/// Returns the smallest `exponent` such that `base ^ exponent ≡ target (mod modulus)`,
+/// or `None` if there is none.
+///
+/// Uses the baby-step giant-step algorithm, which takes about `sqrt(modulus)` time and memory.
+/// `base` must be coprime to `modulus`.
+pub fn discrete_log<T: Integer>(base: T, target: T, modulus: T) -> Option<u64> {
+    let modulus = modulus.into();
+    assert!(modulus > 0, "modulus must be positive");
+    let base = base.into().rem_euclid(modulus);
+    let target = target.into().rem_euclid(modulus);
+    let inverse = mod_inverse_128(base, modulus).expect("base must be coprime to the modulus");
+
+    // Baby steps: base ^ j for j < n, keeping the smallest j for every value
+    let n = (modulus as f64).sqrt().ceil() as u64 + 1;
+    let mut baby_steps = HashMap::new();
+    let mut value = 1 % modulus;
+    for j in 0..n {
+        baby_steps.entry(value).or_insert(j);
+        value = mul_mod_128(value, base, modulus);
+    }
+
+    // Giant steps: target * base ^ (-n * i) for i <= n
+    let giant_step = mod_pow_128(inverse, n, modulus);
+    let mut value = target;
+    for i in 0..=n {
+        if let Some(&j) = baby_steps.get(&value) {
+            return Some(i * n + j);
+        }
+        value = mul_mod_128(value, giant_step, modulus);
+    }
+    None
+}
+
+/// Tests whether `n` is prime, using the Miller-Rabin test with a set of bases
+/// which is deterministic for all 64-bit numbers.
+pub fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&base) = BASES.iter().find(|&&base| n.is_multiple_of(base)) {
+        return n == base;
+    }
+    let n_128 = i128::from(n);
+    // n - 1 = d * 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    BASES.iter().all(|&base| {
+        let mut x = mod_pow_128(base.into(), d, n_128);
+        if x == 1 || x == n_128 - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod_128(x, x, n_128);
+            if x == n_128 - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u64, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        // a * b overflows, but the result fits
+        assert_eq!(lcm(1 << 40, 1i64 << 50), 1 << 50);
+        assert_eq!(lcm(u64::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "lcm overflows")]
+    fn lcm_overflow() {
+        lcm(u64::MAX, u64::MAX - 1);
+    }
+
+    #[test]
+    fn inverses() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6), (2, 1, 1));
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        let m = (1i64 << 61) - 1;
+        let inverse = mod_inverse(m - 2, m).unwrap();
+        assert_eq!(mod_mul(inverse, m - 2, m), 1);
+    }
+
+    #[test]
+    fn powers() {
+        assert_eq!(mod_pow(7u64, 8, 20201227), 5764801);
+        assert_eq!(mod_pow(2, 0, 1), 0);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        let m = u64::MAX - 58; // the largest 64-bit prime
+        assert_eq!(mod_pow(3, m - 1, m), 1);
+        assert_eq!(mod_mul(m - 1, m - 1, m), 1);
+    }
+
+    #[test]
+    fn chinese_remainder_theorem() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Moduli which are not coprime
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+        // Large moduli, whose product does not fit in an i64
+        let (a, b) = (1_000_000_007i64, 998_244_353i64);
+        assert_eq!(
+            crt(&[(5, a), (7, b)]).map(|(x, _)| (x % a, x % b)),
+            Some((5, 7))
+        );
+        assert_eq!(crt(&[(0, a), (0, b), (0, 1_000_000_009)]), None);
+        assert_eq!(crt(&[(0, u64::MAX), (1, u64::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn discrete_logarithm() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+        assert_eq!(discrete_log(2, 1, 7), Some(0));
+        assert_eq!(discrete_log(2, 3, 7), None);
+        assert_eq!(discrete_log(3, 13, 17), Some(4));
+    }
+
+    #[test]
+    fn primes() {
+        let small = (0..100).filter(|&n| is_prime(n)).collect::<Vec<_>>();
+        assert_eq!(small.len(), 25);
+        assert_eq!(small[..6], [2, 3, 5, 7, 11, 13]);
+        assert!(is_prime(20201227));
+        assert!(is_prime(u64::MAX - 58));
+        assert!(!is_prime(3_215_031_751)); // a strong pseudoprime to the bases 2, 3, 5 and 7
+        assert!(!is_prime(u64::MAX));
+    }
 }
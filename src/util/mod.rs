@@ -1,9 +1,21 @@
+pub use automaton::*;
+pub use error::*;
+pub use grid::*;
 pub use math::*;
-pub use vector2d::*;
-pub use vector3d::*;
-pub use vector4d::*;
+pub use normalize::*;
+pub use parser::*;
+pub use render::*;
+pub use search::*;
+pub use trace::*;
+pub use vector::*;
 
+mod automaton;
+mod error;
+mod grid;
 mod math;
-mod vector2d;
-mod vector3d;
-mod vector4d;
+mod normalize;
+mod parser;
+mod render;
+mod search;
+mod trace;
+mod vector;
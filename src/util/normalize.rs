@@ -0,0 +1,91 @@
+use std::borrow::Cow;
+
+use crate::util::ParseError;
+
+const BOM: char = '\u{feff}';
+
+/// Brings a puzzle input into the canonical form that the generators expect:
+/// no byte order mark, `\n` line endings, no trailing whitespace on any line,
+/// and no trailing blank lines or final newline.
+///
+/// Inputs which are already in canonical form are returned without copying.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+    let is_canonical = !input.contains('\r')
+        && !input.ends_with('\n')
+        && input.lines().all(|line| line.trim_end() == line);
+    if is_canonical {
+        return Cow::Borrowed(input);
+    }
+    let mut lines = input.lines().map(str::trim_end).collect::<Vec<_>>();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    Cow::Owned(lines.join("\n"))
+}
+
+/// Checks that a puzzle input is in the canonical form described in [`normalize`],
+/// except for a single final newline, and reports the first deviation.
+pub fn check_normalized(input: &str) -> Result<(), ParseError> {
+    if input.starts_with(BOM) {
+        return Err(ParseError::new("start of input", "\\u{feff}")
+            .with_line(1)
+            .with_column(1));
+    }
+    let content = input.strip_suffix('\n').unwrap_or(input);
+    for (i, line) in content.split('\n').enumerate() {
+        let trimmed = line.trim_end();
+        if trimmed.len() == line.len() {
+            continue;
+        }
+        if line.ends_with('\r') && trimmed.len() == line.len() - 1 {
+            return Err(ParseError::new("`\\n` line ending", "\\r\\n")
+                .with_line(i + 1)
+                .with_column(line.chars().count()));
+        }
+        return Err(ParseError::at(input, &line[trimmed.len()..], "end of line"));
+    }
+    if content.ends_with('\n') {
+        let blank = content.trim_end_matches('\n');
+        let token = &input[blank.len() + 1..=blank.len() + 1];
+        return Err(ParseError::at(input, token, "end of input"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes() {
+        assert!(matches!(normalize("a\nb"), Cow::Borrowed("a\nb")));
+        assert_eq!(normalize("a\nb\n"), "a\nb");
+        assert_eq!(normalize("\u{feff}a\r\nb  \r\n\r\n\n"), "a\nb");
+        assert_eq!(normalize("a \n\nb\t"), "a\n\nb");
+        assert_eq!(normalize("\n\n"), "");
+    }
+
+    #[test]
+    fn strict() {
+        assert_eq!(check_normalized("a\nb"), Ok(()));
+        assert_eq!(check_normalized("a\nb\n"), Ok(()));
+        let error = check_normalized("a\r\nb").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 2: expected `\\n` line ending, found `\\r\\n`"
+        );
+        let error = check_normalized("a\nb  \nc").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected end of line, found `  `"
+        );
+        let error = check_normalized("a\nb\n\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected end of input, found end of line"
+        );
+        let error = check_normalized("\u{feff}a").unwrap_err();
+        assert_eq!((error.line(), error.column()), (Some(1), Some(1)));
+    }
+}
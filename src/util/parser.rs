@@ -0,0 +1,411 @@
+//! A small parser-combinator library for the puzzle input formats.
+//!
+//! A parser is anything implementing [`Parser`], usually a closure or one of the functions below,
+//! and is run on a complete puzzle input with [`parse`]. Errors point at the offending token
+//! with its line and column in the complete input.
+//!
+//! ```
+//! use advent_of_code_2020::util::{integer, lines, literal, parse, satisfy, word, Parser};
+//!
+//! # fn main() -> Result<(), advent_of_code_2020::ParseError> {
+//! let input = "1-3 a: abcde\n2-9 c: ccccccccc";
+//! // 1-3 a
+//! let policy = integer::<usize>()
+//!     .skip(literal("-"))
+//!     .then(integer::<usize>())
+//!     .skip(literal(" "))
+//!     .then(satisfy("a letter", char::is_alphabetic));
+//! let entries = parse(input, lines(policy.skip(literal(": ")).then(word())))?;
+//! assert_eq!(entries[0], (((1, 3), 'a'), "abcde"));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::str::FromStr;
+
+use crate::util::ParseError;
+
+/// The remaining input of a parser, together with the complete source for error positions.
+#[derive(Debug, Copy, Clone)]
+pub struct Input<'a> {
+    source: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(source: &'a str) -> Input<'a> {
+        Input {
+            source,
+            rest: source,
+        }
+    }
+
+    /// Returns the input which has not been parsed yet.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    /// Splits off the first `len` bytes of the remaining input.
+    pub fn advance(self, len: usize) -> (&'a str, Input<'a>) {
+        let (token, rest) = self.rest.split_at(len);
+        (token, Input { rest, ..self })
+    }
+
+    /// Returns the part of the input which was consumed between `self` and `later`.
+    pub fn consumed(&self, later: Input<'a>) -> &'a str {
+        &self.rest[..self.rest.len() - later.rest.len()]
+    }
+
+    /// Creates an error at the next token of the remaining input.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        ParseError::at(self.source, next_token(self.rest), expected)
+    }
+
+    /// Creates an error for `token`, which must be a slice of the source.
+    pub fn error_at(&self, token: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::at(self.source, token, expected)
+    }
+}
+
+/// Returns the word or the single character at the start of `s`, for error messages.
+fn next_token(s: &str) -> &str {
+    let len = s.find(|c: char| !c.is_alphanumeric()).unwrap_or(s.len());
+    if len > 0 {
+        &s[..len]
+    } else {
+        &s[..s.chars().next().map_or(0, char::len_utf8)]
+    }
+}
+
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+pub trait Parser<'a, T> {
+    fn parse(&self, input: Input<'a>) -> ParseResult<'a, T>;
+
+    /// Borrows this parser, so that it can be used more than once.
+    fn by_ref(&self) -> impl Parser<'a, T> + '_ {
+        move |input: Input<'a>| self.parse(input)
+    }
+
+    /// Converts the parsed value with `f`.
+    fn map<U, F>(self, f: F) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+        F: Fn(T) -> U,
+    {
+        move |input: Input<'a>| {
+            let (value, rest) = self.parse(input)?;
+            Ok((f(value), rest))
+        }
+    }
+
+    /// Converts the parsed value with `f`, or reports `expected` for the consumed input
+    /// if `f` returns `None`.
+    fn try_map<U, F>(self, expected: impl Into<String>, f: F) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+        F: Fn(T) -> Option<U>,
+    {
+        let expected = expected.into();
+        move |input: Input<'a>| {
+            let (value, rest) = self.parse(input)?;
+            match f(value) {
+                Some(value) => Ok((value, rest)),
+                None => Err(input.error_at(input.consumed(rest), expected.clone())),
+            }
+        }
+    }
+
+    /// Parses `next` after this parser, and returns both values.
+    fn then<U, P>(self, next: P) -> impl Parser<'a, (T, U)>
+    where
+        Self: Sized,
+        P: Parser<'a, U>,
+    {
+        move |input: Input<'a>| {
+            let (first, rest) = self.parse(input)?;
+            let (second, rest) = next.parse(rest)?;
+            Ok(((first, second), rest))
+        }
+    }
+
+    /// Parses `next` after this parser, and ignores its value.
+    fn skip<U, P>(self, next: P) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+        P: Parser<'a, U>,
+    {
+        self.then(next).map(|(value, _)| value)
+    }
+
+    /// Tries this parser, and `other` from the same position if this parser fails.
+    fn or<P>(self, other: P) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+        P: Parser<'a, T>,
+    {
+        move |input: Input<'a>| {
+            self.parse(input)
+                .or_else(|e| other.parse(input).map_err(|other| e.or(other)))
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    fn parse(&self, input: Input<'a>) -> ParseResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Runs `parser` on the complete `source`, which must be consumed completely.
+pub fn parse<'a, T>(source: &'a str, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+    let (value, rest) = parser.parse(Input::new(source))?;
+    if !rest.rest().is_empty() {
+        return Err(rest.error("end of input"));
+    }
+    Ok(value)
+}
+
+/// Matches the exact string `lit`.
+pub fn literal<'a>(lit: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        if input.rest().starts_with(lit) {
+            Ok(input.advance(lit.len()))
+        } else {
+            Err(input.error(format!("`{}`", lit.escape_debug())))
+        }
+    }
+}
+
+/// Matches a single character for which `predicate` returns true.
+pub fn satisfy<'a, F>(expected: &'static str, predicate: F) -> impl Parser<'a, char>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: Input<'a>| match input.rest().chars().next() {
+        Some(c) if predicate(c) => Ok((c, input.advance(c.len_utf8()).1)),
+        _ => Err(input.error(expected)),
+    }
+}
+
+/// Matches one or more characters for which `predicate` returns true.
+pub fn take_while1<'a, F>(expected: &'static str, predicate: F) -> impl Parser<'a, &'a str>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(input.error(expected));
+        }
+        Ok(input.advance(len))
+    }
+}
+
+/// Matches an integer with an optional sign, e.g. `42`, `-3` or `+7`.
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let sign_len = usize::from(rest.starts_with(&['+', '-'][..]));
+        let digits_len = rest[sign_len..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - sign_len);
+        if digits_len == 0 {
+            return Err(input.error("integer"));
+        }
+        let (token, rest) = input.advance(sign_len + digits_len);
+        let value = token
+            .parse()
+            .map_err(|_| input.error_at(token, "integer in range"))?;
+        Ok((value, rest))
+    }
+}
+
+/// Matches an identifier made of letters, digits and underscores.
+pub fn identifier<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("identifier", |c| c.is_alphanumeric() || c == '_')
+}
+
+/// Matches everything up to the next whitespace.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("word", |c| !c.is_whitespace())
+}
+
+/// Parses `prefix` and then `parser`, and returns the value of `parser`.
+pub fn preceded<'a, T, U>(
+    prefix: impl Parser<'a, U>,
+    parser: impl Parser<'a, T>,
+) -> impl Parser<'a, T> {
+    prefix.then(parser).map(|(_, value)| value)
+}
+
+/// Tries `parser`, and returns `None` without consuming any input if it fails.
+pub fn optional<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: Input<'a>| match parser.parse(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+/// Parses `parser` as often as possible, zero or more times.
+/// Stops when `parser` succeeds without consuming any input, which would repeat forever.
+pub fn many<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: Input<'a>| {
+        let mut values = vec![];
+        while let Ok((value, rest)) = parser.parse(input) {
+            if rest.rest().len() == input.rest().len() {
+                break;
+            }
+            values.push(value);
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+/// Parses one or more `item`s separated by `separator`.
+/// An `item` must follow every separator. Stops when a separator and its `item` succeed
+/// without consuming any input, which would repeat forever.
+pub fn separated<'a, T, U>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, U>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: Input<'a>| {
+        let (first, mut input) = item.parse(input)?;
+        let mut values = vec![first];
+        while let Ok((_, rest)) = separator.parse(input) {
+            let (value, rest) = item.parse(rest)?;
+            if rest.rest().len() == input.rest().len() {
+                break;
+            }
+            values.push(value);
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+/// Parses one or more lines with `line`, up to a blank line or the end of the input.
+/// Every line must be parsed completely.
+pub fn lines<'a, T>(line: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: Input<'a>| {
+        let mut values = vec![];
+        loop {
+            let (value, rest) = line.parse(input)?;
+            values.push(value);
+            input = rest;
+            match input.rest().strip_prefix('\n') {
+                Some(next) if !next.is_empty() && !next.starts_with('\n') => {
+                    input = input.advance(1).1;
+                }
+                Some(_) => return Ok((values, input)),
+                None if input.rest().is_empty() => return Ok((values, input)),
+                None => return Err(input.error("end of line")),
+            }
+        }
+    }
+}
+
+/// Parses one or more blocks of lines with `block`, separated by blank lines.
+pub fn blocks<'a, T>(block: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(block, literal("\n\n"))
+}
+
+/// Parses a `header` line, followed by the lines of the section with `body`.
+pub fn section<'a, T>(header: &'static str, body: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    preceded(literal(header).then(literal("\n")), body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range<'a>() -> impl Parser<'a, (i32, i32)> {
+        integer().skip(literal("-")).then(integer())
+    }
+
+    #[test]
+    fn sequences() {
+        assert_eq!(parse("1-3", range()), Ok((1, 3)));
+        assert_eq!(parse("-1--3", range()), Ok((-1, -3)));
+        let error = parse("1-x", range()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 3: expected integer, found `x`"
+        );
+        let error = parse("1-3 ", range()).unwrap_err();
+        assert_eq!(error.column(), Some(4));
+        let error = parse("99999999999-1", range()).unwrap_err();
+        assert_eq!(error.expected(), "integer in range");
+    }
+
+    #[test]
+    fn lists_and_lines() {
+        let tickets = lines(separated(integer::<u32>(), literal(",")));
+        assert_eq!(
+            parse("1,2\n3", tickets.by_ref()),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        let error = parse("1,2\n3,", tickets.by_ref()).unwrap_err();
+        assert_eq!((error.line(), error.column()), (Some(2), Some(3)));
+        let error = parse("1,2\n3 4", tickets.by_ref()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected end of line, found ` `"
+        );
+        let words = many(preceded(literal(" "), identifier()));
+        assert_eq!(parse(" a b", words), Ok(vec!["a", "b"]));
+    }
+
+    #[test]
+    fn repetitions_without_progress() {
+        let digits = many(optional(literal("1")));
+        assert_eq!(parse("11", digits.by_ref()), Ok(vec![Some("1"), Some("1")]));
+        let error = parse("12", digits.by_ref()).unwrap_err();
+        assert_eq!(error.column(), Some(2));
+        let numbers = separated(optional(integer::<u32>()), optional(literal(",")));
+        assert_eq!(parse("1,2", numbers.by_ref()), Ok(vec![Some(1), Some(2)]));
+        assert_eq!(parse("", numbers), Ok(vec![None]));
+    }
+
+    #[test]
+    fn blocks_and_sections() {
+        let deck = |header| section(header, lines(integer::<u32>()));
+        let decks = deck("Player 1:")
+            .skip(literal("\n\n"))
+            .then(deck("Player 2:"));
+        assert_eq!(
+            parse("Player 1:\n1\n2\n\nPlayer 2:\n3", decks),
+            Ok((vec![1, 2], vec![3]))
+        );
+        let groups = blocks(lines(identifier()));
+        assert_eq!(
+            parse("a\nb\n\nc", groups.by_ref()),
+            Ok(vec![vec!["a", "b"], vec!["c"]])
+        );
+        let error = parse("a\nb\n\n\nc", groups.by_ref()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: expected identifier, found end of line"
+        );
+    }
+
+    #[test]
+    fn alternatives() {
+        let answer = literal("yes")
+            .map(|_| true)
+            .or(literal("no").map(|_| false));
+        assert_eq!(parse("no", answer.by_ref()), Ok(false));
+        let error = parse("maybe", answer.by_ref()).unwrap_err();
+        assert_eq!(error.expected(), "`yes` or `no`");
+        let number = optional(integer::<u8>());
+        assert_eq!(parse("", number.by_ref()), Ok(None));
+        let even = integer::<u8>().try_map("even number", |n| Some(n).filter(|n| n % 2 == 0));
+        let error = parse("17", even).unwrap_err();
+        assert_eq!(error.found(), "17");
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::util::{Grid, ParseError};
+
+/// A color as red, green and blue components.
+pub type Rgb = [u8; 3];
+
+/// The colors for the values of a rendered grid: value `i` gets color `i`.
+/// Values beyond the end of the palette wrap around.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Palette(pub Vec<Rgb>);
+
+impl Palette {
+    pub fn color(&self, value: u8) -> Rgb {
+        self.0[usize::from(value) % self.0.len()]
+    }
+}
+
+impl FromStr for Palette {
+    type Err = ParseError;
+
+    /// Parses a comma-separated list of hex colors, like `000000,ffffff,ff0000`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|color| {
+                let hex = color.strip_prefix('#').unwrap_or(color);
+                let component = |i: usize| {
+                    hex.get(2 * i..2 * i + 2)
+                        .and_then(|c| u8::from_str_radix(c, 16).ok())
+                };
+                match (hex.len(), component(0), component(1), component(2)) {
+                    (6, Some(r), Some(g), Some(b)) => Ok([r, g, b]),
+                    _ => Err(ParseError::at(s, color, "color like `ff8800`")),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Palette)
+    }
+}
+
+/// The image file format of rendered frames.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ImageFormat {
+    /// Plain PPM (`P3`), in color.
+    #[default]
+    Ppm,
+    /// Plain PGM (`P2`), in grayscale.
+    Pgm,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Pgm => "pgm",
+        }
+    }
+}
+
+/// How grids are turned into images.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RenderOptions {
+    /// The width and height of every cell, in pixels.
+    pub scale: usize,
+    /// Replaces the default palette of the rendered day.
+    pub palette: Option<Palette>,
+    pub format: ImageFormat,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            scale: 4,
+            palette: None,
+            format: ImageFormat::Ppm,
+        }
+    }
+}
+
+/// Encodes a grid of palette indices as an image file, with every cell as a square of pixels.
+///
+/// Writes the plain (ASCII) variants of the formats, with every row of pixels
+/// on its own lines of at most 70 characters.
+pub fn encode(grid: &Grid<u8>, palette: &Palette, scale: usize, format: ImageFormat) -> Vec<u8> {
+    let (width, height) = (grid.width() * scale, grid.height() * scale);
+    let magic = match format {
+        ImageFormat::Ppm => "P3",
+        ImageFormat::Pgm => "P2",
+    };
+    let mut text = format!("{}\n{} {}\n255\n", magic, width, height);
+    for row in grid.rows() {
+        let mut samples = vec![];
+        for &value in row {
+            let [r, g, b] = palette.color(value);
+            for _ in 0..scale {
+                match format {
+                    ImageFormat::Ppm => samples.extend_from_slice(&[r, g, b]),
+                    // Rec. 601 luma
+                    ImageFormat::Pgm => samples.push(
+                        ((299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000)
+                            as u8,
+                    ),
+                }
+            }
+        }
+        let mut lines = String::new();
+        let mut line_len = 0;
+        for sample in samples {
+            let sample = sample.to_string();
+            if line_len > 0 && line_len + 1 + sample.len() > 70 {
+                lines.push('\n');
+                line_len = 0;
+            } else if line_len > 0 {
+                lines.push(' ');
+                line_len += 1;
+            }
+            lines.push_str(&sample);
+            line_len += sample.len();
+        }
+        lines.push('\n');
+        for _ in 0..scale {
+            text.push_str(&lines);
+        }
+    }
+    text.into_bytes()
+}
+
+/// Writes numbered frames to a directory, such as `part1-0000.ppm`, `part1-0001.ppm`, ...
+#[derive(Debug)]
+pub struct Frames {
+    dir: PathBuf,
+    options: RenderOptions,
+    counts: HashMap<String, usize>,
+}
+
+impl Frames {
+    /// Creates the directory if needed.
+    pub fn new(dir: impl Into<PathBuf>, options: RenderOptions) -> io::Result<Frames> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Frames {
+            dir,
+            options,
+            counts: HashMap::new(),
+        })
+    }
+
+    /// Writes the next frame of the sequence `name`, using `palette` unless the options
+    /// replace it.
+    pub fn write(&mut self, name: &str, grid: &Grid<u8>, palette: &Palette) -> io::Result<()> {
+        let palette = self.options.palette.as_ref().unwrap_or(palette);
+        let bytes = encode(grid, palette, self.options.scale, self.options.format);
+        let count = self.counts.entry(name.to_owned()).or_default();
+        let file = format!("{}-{:04}.{}", name, count, self.options.format.extension());
+        *count += 1;
+        fs::write(self.dir.join(file), bytes)
+    }
+
+    /// Returns the total number of frames written.
+    pub fn len(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palettes() {
+        let palette = "000000,#FF8000".parse::<Palette>().unwrap();
+        assert_eq!(palette, Palette(vec![[0, 0, 0], [255, 128, 0]]));
+        assert_eq!(palette.color(3), [255, 128, 0]);
+        let error = "000000,fff".parse::<Palette>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 8: expected color like `ff8800`, found `fff`"
+        );
+    }
+
+    #[test]
+    fn encoding() {
+        let grid = Grid::new(2, 1, vec![0, 1]);
+        let palette = Palette(vec![[0, 0, 0], [255, 0, 10]]);
+        let ppm = encode(&grid, &palette, 1, ImageFormat::Ppm);
+        assert_eq!(ppm, b"P3\n2 1\n255\n0 0 0 255 0 10\n");
+        let pgm = encode(&grid, &palette, 2, ImageFormat::Pgm);
+        assert_eq!(pgm, b"P2\n4 2\n255\n0 0 77 77\n0 0 77 77\n");
+        let wide = Grid::new(30, 1, vec![1; 30]);
+        let ppm = String::from_utf8(encode(&wide, &palette, 1, ImageFormat::Ppm)).unwrap();
+        assert!(ppm.lines().all(|line| line.len() <= 70));
+        assert_eq!(ppm.split_whitespace().count(), 4 + 30 * 3);
+    }
+}
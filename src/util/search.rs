@@ -0,0 +1,207 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A search problem which is solved by making one choice at a time,
+/// and undoing choices that lead to a dead end.
+///
+/// The state is modified in place, so nothing has to be cloned per branch.
+pub trait Backtrack {
+    type Choice;
+
+    /// Returns true if the state is a complete solution.
+    fn is_complete(&self) -> bool;
+
+    /// Returns the valid choices for the next step, in the order in which they should be tried.
+    fn choices(&self) -> Vec<Self::Choice>;
+
+    fn apply(&mut self, choice: &Self::Choice);
+
+    /// Reverts a choice, which is always the last applied choice.
+    fn undo(&mut self, choice: &Self::Choice);
+}
+
+/// Searches for a complete solution, depth-first.
+///
+/// Returns true if a solution was found, with the state left at that solution,
+/// or false if there is none, with the state back where it started.
+pub fn backtrack<S: Backtrack>(state: &mut S) -> bool {
+    if state.is_complete() {
+        return true;
+    }
+    for choice in state.choices() {
+        state.apply(&choice);
+        if backtrack(state) {
+            return true;
+        }
+        state.undo(&choice);
+    }
+    false
+}
+
+/// Visits all nodes reachable from the start nodes in breadth-first order,
+/// and returns every node together with its distance to the nearest start node.
+pub fn bfs<N, I, F>(starts: impl IntoIterator<Item = N>, mut neighbours: F) -> Vec<(N, usize)>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+{
+    let mut visited = HashSet::new();
+    let mut queue = starts
+        .into_iter()
+        .filter(|node| visited.insert(node.clone()))
+        .map(|node| (node, 0))
+        .collect::<VecDeque<_>>();
+    let mut order = vec![];
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if visited.insert(next.clone()) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+        order.push((node, distance));
+    }
+    order
+}
+
+/// Visits all nodes reachable from the start nodes in depth-first order,
+/// and returns them in the order in which they were visited.
+pub fn dfs<N, I, F>(starts: impl IntoIterator<Item = N>, mut neighbours: F) -> Vec<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+{
+    let mut visited = HashSet::new();
+    let mut stack = starts.into_iter().collect::<Vec<_>>();
+    stack.reverse();
+    let mut order = vec![];
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        let mut next = neighbours(&node)
+            .into_iter()
+            .filter(|next| !visited.contains(next))
+            .collect::<Vec<_>>();
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+    order
+}
+
+/// Computes a recursive function with memoization.
+///
+/// `f` receives a function to compute the value for another key (which is cached),
+/// and the key to compute the value for. For example:
+///
+/// ```
+/// # use advent_of_code_2020::util::memoize;
+/// let fibonacci = memoize(80u64, |fibonacci, n| {
+///     if n < 2 {
+///         n
+///     } else {
+///         fibonacci(n - 1) + fibonacci(n - 2)
+///     }
+/// });
+/// assert_eq!(fibonacci, 23_416_728_348_467_685);
+/// ```
+pub fn memoize<K, V, F>(key: K, f: F) -> V
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    fn get<K, V, F>(key: K, f: &F, cache: &mut HashMap<K, V>) -> V
+    where
+        K: Eq + Hash + Clone,
+        V: Clone,
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        if let Some(value) = cache.get(&key) {
+            return value.clone();
+        }
+        let value = f(&mut |key| get(key, f, cache), key.clone());
+        cache.insert(key, value.clone());
+        value
+    }
+    get(key, &f, &mut HashMap::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Places `n` queens on an `n` by `n` board, one row at a time.
+    struct Queens {
+        n: usize,
+        columns: Vec<usize>,
+    }
+
+    impl Backtrack for Queens {
+        type Choice = usize;
+
+        fn is_complete(&self) -> bool {
+            self.columns.len() == self.n
+        }
+
+        fn choices(&self) -> Vec<usize> {
+            let row = self.columns.len();
+            (0..self.n)
+                .filter(|&column| {
+                    self.columns.iter().enumerate().all(|(r, &c)| {
+                        c != column && row - r != (c as isize - column as isize).unsigned_abs()
+                    })
+                })
+                .collect()
+        }
+
+        fn apply(&mut self, &column: &usize) {
+            self.columns.push(column);
+        }
+
+        fn undo(&mut self, _: &usize) {
+            self.columns.pop();
+        }
+    }
+
+    #[test]
+    fn queens() {
+        let mut queens = Queens {
+            n: 8,
+            columns: vec![],
+        };
+        assert!(backtrack(&mut queens));
+        assert_eq!(queens.columns, vec![0, 4, 7, 5, 2, 6, 1, 3]);
+        let mut queens = Queens {
+            n: 3,
+            columns: vec![],
+        };
+        assert!(!backtrack(&mut queens));
+        assert!(queens.columns.is_empty());
+    }
+
+    #[test]
+    fn graph_search() {
+        let edges = |&n: &u32| vec![n * 2 % 10, n + 1].into_iter().filter(|&m| m < 6);
+        assert_eq!(
+            bfs(vec![1], edges),
+            vec![(1, 0), (2, 1), (4, 2), (3, 2), (5, 3), (0, 4)]
+        );
+        assert_eq!(dfs(vec![1], edges), vec![1, 2, 4, 5, 0, 3]);
+        assert_eq!(dfs(vec![1, 1], |_| vec![]), vec![1]);
+    }
+
+    #[test]
+    fn memoized() {
+        let paths = memoize((10, 10), |paths, (x, y): (u64, u64)| {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                paths((x - 1, y)) + paths((x, y - 1))
+            }
+        });
+        assert_eq!(paths, 184_756);
+    }
+}
@@ -0,0 +1,260 @@
+//! Structured events from step-by-step simulations, for debugging wrong answers.
+//!
+//! A trace file has one event per line: the step number, the kind of event and its fields,
+//! like `12 exec pc=3 op=acc arg=1 acc=5`. Lines starting with `#` are comments.
+
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::util::ParseError;
+
+/// An event of a simulation, such as an executed instruction or a flipped tile.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraceEvent {
+    /// The number of the event in its trace, starting at 0.
+    pub step: u64,
+    pub kind: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl TraceEvent {
+    pub fn new(kind: &str) -> TraceEvent {
+        TraceEvent {
+            step: 0,
+            kind: kind.to_owned(),
+            fields: vec![],
+        }
+    }
+
+    /// Adds a field. Values must not contain whitespace, so lists are joined with commas.
+    /// Panics if the value contains whitespace, since the trace could not be read back.
+    pub fn with(mut self, name: &str, value: impl Display) -> TraceEvent {
+        let value = value.to_string();
+        assert!(
+            !value.contains(char::is_whitespace),
+            "trace value {:?} contains whitespace",
+            value
+        );
+        self.fields.push((name.to_owned(), value));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Display for TraceEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.step, self.kind)?;
+        for (name, value) in &self.fields {
+            write!(f, " {}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TraceEvent {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let step = tokens.next().ok_or_else(|| ParseError::at(s, s, "step"))?;
+        let step = step.parse().map_err(|_| ParseError::at(s, step, "step"))?;
+        let kind = tokens.next().ok_or_else(|| ParseError::at(s, s, "kind"))?;
+        let fields = tokens
+            .map(|field| {
+                field
+                    .split_once('=')
+                    .map(|(name, value)| (name.to_owned(), value.to_owned()))
+                    .ok_or_else(|| ParseError::at(s, field, "field like `name=value`"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(TraceEvent {
+            step,
+            kind: kind.to_owned(),
+            fields,
+        })
+    }
+}
+
+/// Parses a trace file, skipping comments and blank lines.
+pub fn read_trace(input: &str) -> Result<Vec<TraceEvent>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| line.parse().map_err(|e: ParseError| e.offset_lines(i)))
+        .collect()
+}
+
+/// Receives the events of a simulation.
+///
+/// Events are passed as closures, so that simulations which are not traced
+/// (with [`NoTrace`]) don't pay for building them.
+pub trait Trace {
+    fn record(&mut self, event: impl FnOnce() -> TraceEvent);
+}
+
+/// Ignores all events.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct NoTrace;
+
+impl Trace for NoTrace {
+    #[inline]
+    fn record(&mut self, _: impl FnOnce() -> TraceEvent) {}
+}
+
+/// Collects the events in memory, numbered in order.
+impl Trace for Vec<TraceEvent> {
+    fn record(&mut self, event: impl FnOnce() -> TraceEvent) {
+        let mut event = event();
+        event.step = self.len() as u64;
+        self.push(event);
+    }
+}
+
+/// Writes the events as a trace file, numbered in order.
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    out: W,
+    steps: u64,
+    /// The first write error, which is reported by [`Recorder::finish`].
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(out: W) -> Recorder<W> {
+        Recorder {
+            out,
+            steps: 0,
+            error: None,
+        }
+    }
+
+    /// Writes a comment line, such as a header.
+    pub fn comment(&mut self, text: &str) {
+        let result = writeln!(self.out, "# {}", text);
+        self.check(result);
+    }
+
+    /// Returns the number of events written.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Flushes the output, and returns the first error that occurred while writing.
+    pub fn finish(mut self) -> io::Result<W> {
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => self.out.flush().map(|_| self.out),
+        }
+    }
+
+    fn check(&mut self, result: io::Result<()>) {
+        if let Err(error) = result {
+            self.error.get_or_insert(error);
+        }
+    }
+}
+
+impl<W: Write> Trace for Recorder<W> {
+    fn record(&mut self, event: impl FnOnce() -> TraceEvent) {
+        if self.error.is_some() {
+            return;
+        }
+        let mut event = event();
+        event.step = self.steps;
+        self.steps += 1;
+        let result = writeln!(self.out, "{}", event);
+        self.check(result);
+    }
+}
+
+/// Selects events of a trace by kind, field values and step numbers.
+/// An empty filter matches all events.
+#[derive(Debug, Clone, Default)]
+pub struct TraceFilter {
+    /// Matches any of these kinds, or all kinds if empty.
+    pub kinds: Vec<String>,
+    /// Matches events which have all of these field values.
+    pub fields: Vec<(String, String)>,
+    pub steps: Option<RangeInclusive<u64>>,
+}
+
+impl TraceFilter {
+    pub fn matches(&self, event: &TraceEvent) -> bool {
+        (self.kinds.is_empty() || self.kinds.contains(&event.kind))
+            && self
+                .fields
+                .iter()
+                .all(|(name, value)| event.get(name) == Some(value))
+            && self
+                .steps
+                .as_ref()
+                .is_none_or(|steps| steps.contains(&event.step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events() {
+        let event = TraceEvent::new("exec").with("pc", 3).with("op", "acc");
+        assert_eq!(event.to_string(), "0 exec pc=3 op=acc");
+        assert_eq!("0 exec pc=3 op=acc".parse(), Ok(event));
+        let error = read_trace("# header\n\n1 exec\n2 exec pc").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 8: expected field like `name=value`, found `pc`"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "contains whitespace")]
+    fn value_with_whitespace() {
+        TraceEvent::new("exec").with("op", "acc +1");
+    }
+
+    #[test]
+    fn recording_and_filtering() {
+        let mut recorder = Recorder::new(vec![]);
+        recorder.comment("2020 day 8 part 1");
+        for pc in 0..5 {
+            recorder.record(|| {
+                TraceEvent::new(if pc % 2 == 0 { "even" } else { "odd" }).with("pc", pc)
+            });
+        }
+        assert_eq!(recorder.steps(), 5);
+        let output = String::from_utf8(recorder.finish().unwrap()).unwrap();
+        assert!(output.starts_with("# 2020 day 8 part 1\n0 even pc=0\n1 odd pc=1\n"));
+
+        let events = read_trace(&output).unwrap();
+        let filter = TraceFilter {
+            kinds: vec!["even".to_owned()],
+            fields: vec![],
+            steps: Some(1..=4),
+        };
+        let steps = |filter: &TraceFilter| {
+            events
+                .iter()
+                .filter(|event| filter.matches(event))
+                .map(|event| event.step)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(steps(&filter), vec![2, 4]);
+        let filter = TraceFilter {
+            fields: vec![("pc".to_owned(), "3".to_owned())],
+            ..TraceFilter::default()
+        };
+        assert_eq!(steps(&filter), vec![3]);
+        assert_eq!(steps(&TraceFilter::default()).len(), 5);
+    }
+}
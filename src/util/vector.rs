@@ -0,0 +1,299 @@
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+
+/// A vector with `D` integer components.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct VectorN<const D: usize>(pub [i32; D]);
+
+pub type Vector2D = VectorN<2>;
+pub type Vector3D = VectorN<3>;
+pub type Vector4D = VectorN<4>;
+
+impl<const D: usize> VectorN<D> {
+    pub const fn zero() -> Self {
+        VectorN([0; D])
+    }
+
+    /// Returns the vector with the given component set to 1 and all others set to 0.
+    pub fn unit(axis: usize) -> Self {
+        let mut vector = Self::zero();
+        vector[axis] = 1;
+        vector
+    }
+
+    pub fn dot(&self, other: Self) -> i32 {
+        self.0.iter().zip(other.0.iter()).map(|(a, b)| a * b).sum()
+    }
+
+    /// Returns the Manhattan (L1) norm, the sum of the absolute components.
+    pub fn manhattan_distance(&self) -> i32 {
+        self.0.iter().map(|x| x.abs()).sum()
+    }
+
+    /// Returns the Chebyshev (L∞) norm, the largest absolute component.
+    pub fn chebyshev_distance(&self) -> i32 {
+        self.0.iter().map(|x| x.abs()).max().unwrap_or(0)
+    }
+
+    /// Returns the component-wise minimum.
+    pub fn min(&self, other: Self) -> Self {
+        let mut result = *self;
+        for (a, b) in result.0.iter_mut().zip(other.0.iter()) {
+            *a = (*a).min(*b);
+        }
+        result
+    }
+
+    /// Returns the component-wise maximum.
+    pub fn max(&self, other: Self) -> Self {
+        let mut result = *self;
+        for (a, b) in result.0.iter_mut().zip(other.0.iter()) {
+            *a = (*a).max(*b);
+        }
+        result
+    }
+
+    /// Returns the smallest and largest corner of the bounding box of the given vectors,
+    /// or `None` if there are no vectors.
+    pub fn bounds(vectors: impl IntoIterator<Item = Self>) -> Option<(Self, Self)> {
+        vectors.into_iter().fold(None, |bounds, v| match bounds {
+            None => Some((v, v)),
+            Some((min, max)) => Some((min.min(v), max.max(v))),
+        })
+    }
+
+    /// Returns the 2×D steps to the orthogonally adjacent positions
+    /// (the von Neumann neighbourhood).
+    pub fn orthogonal_neighbours() -> Vec<Self> {
+        (0..D)
+            .flat_map(|axis| vec![Self::unit(axis), -Self::unit(axis)])
+            .collect()
+    }
+
+    /// Returns the 3^D - 1 steps to all adjacent positions, including diagonals
+    /// (the Moore neighbourhood).
+    pub fn neighbours() -> Vec<Self> {
+        let mut steps = vec![Self::zero()];
+        for axis in 0..D {
+            steps = steps
+                .into_iter()
+                .flat_map(|step| {
+                    (-1..=1).map(move |offset| {
+                        let mut step = step;
+                        step[axis] = offset;
+                        step
+                    })
+                })
+                .collect();
+        }
+        steps.retain(|&step| step != Self::zero());
+        steps
+    }
+
+    /// Converts into a vector with `E` components, dropping extra components
+    /// or padding with zeros.
+    pub fn resize<const E: usize>(&self) -> VectorN<E> {
+        let mut result = VectorN::zero();
+        for (a, b) in result.0.iter_mut().zip(self.0.iter()) {
+            *a = *b;
+        }
+        result
+    }
+}
+
+impl VectorN<2> {
+    pub const fn new(x: i32, y: i32) -> Self {
+        VectorN([x, y])
+    }
+
+    pub fn x(&self) -> i32 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i32 {
+        self.0[1]
+    }
+}
+
+impl VectorN<3> {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        VectorN([x, y, z])
+    }
+
+    pub fn x(&self) -> i32 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i32 {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> i32 {
+        self.0[2]
+    }
+}
+
+impl VectorN<4> {
+    pub const fn new(x: i32, y: i32, z: i32, w: i32) -> Self {
+        VectorN([x, y, z, w])
+    }
+
+    pub fn x(&self) -> i32 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i32 {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> i32 {
+        self.0[2]
+    }
+
+    pub fn w(&self) -> i32 {
+        self.0[3]
+    }
+}
+
+impl<const D: usize> Default for VectorN<D> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<const D: usize> From<[i32; D]> for VectorN<D> {
+    fn from(components: [i32; D]) -> Self {
+        VectorN(components)
+    }
+}
+
+impl<const D: usize> Index<usize> for VectorN<D> {
+    type Output = i32;
+
+    fn index(&self, axis: usize) -> &i32 {
+        &self.0[axis]
+    }
+}
+
+impl<const D: usize> IndexMut<usize> for VectorN<D> {
+    fn index_mut(&mut self, axis: usize) -> &mut i32 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const D: usize> AddAssign for VectorN<D> {
+    fn add_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a += b;
+        }
+    }
+}
+
+impl<const D: usize> SubAssign for VectorN<D> {
+    fn sub_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a -= b;
+        }
+    }
+}
+
+impl<const D: usize> MulAssign<i32> for VectorN<D> {
+    fn mul_assign(&mut self, rhs: i32) {
+        for a in self.0.iter_mut() {
+            *a *= rhs;
+        }
+    }
+}
+
+impl<const D: usize> DivAssign<i32> for VectorN<D> {
+    fn div_assign(&mut self, rhs: i32) {
+        for a in self.0.iter_mut() {
+            *a /= rhs;
+        }
+    }
+}
+
+impl<const D: usize> Add for VectorN<D> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<const D: usize> Sub for VectorN<D> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl<const D: usize> Mul<i32> for VectorN<D> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: i32) -> Self {
+        self *= rhs;
+        self
+    }
+}
+
+impl<const D: usize> Div<i32> for VectorN<D> {
+    type Output = Self;
+
+    fn div(mut self, rhs: i32) -> Self {
+        self /= rhs;
+        self
+    }
+}
+
+impl<const D: usize> Neg for VectorN<D> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self * -1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        let a = Vector3D::new(1, -2, 3);
+        let b = Vector3D::new(4, 5, -6);
+        assert_eq!(a + b, Vector3D::new(5, 3, -3));
+        assert_eq!(a - b, Vector3D::new(-3, -7, 9));
+        assert_eq!(-a, Vector3D::new(-1, 2, -3));
+        assert_eq!(a * 2, Vector3D::new(2, -4, 6));
+        assert_eq!(b / 2, Vector3D::new(2, 2, -3));
+        assert_eq!(a.dot(b), 4 - 10 - 18);
+    }
+
+    #[test]
+    fn norms_and_bounds() {
+        let a = Vector4D::new(1, -7, 3, 0);
+        assert_eq!(a.manhattan_distance(), 11);
+        assert_eq!(a.chebyshev_distance(), 7);
+        let bounds = Vector2D::bounds(vec![
+            Vector2D::new(1, 5),
+            Vector2D::new(-2, 3),
+            Vector2D::new(4, -1),
+        ]);
+        assert_eq!(bounds, Some((Vector2D::new(-2, -1), Vector2D::new(4, 5))));
+        assert_eq!(Vector2D::bounds(vec![]), None);
+    }
+
+    #[test]
+    fn neighbours() {
+        assert_eq!(VectorN::<2>::orthogonal_neighbours().len(), 4);
+        assert_eq!(VectorN::<3>::neighbours().len(), 26);
+        let neighbours = VectorN::<5>::neighbours();
+        assert_eq!(neighbours.len(), 242);
+        assert!(neighbours.iter().all(|v| v.chebyshev_distance() == 1));
+    }
+}
@@ -0,0 +1,310 @@
+use std::error::Error;
+
+use crate::util::{normalize, parse_lines, parse_token, ParseError};
+use crate::Explanation;
+
+#[aoc_generator(day1)]
+pub fn input_generator(input: &str) -> Result<Vec<i32>, ParseError> {
+    let input = &normalize(input);
+    parse_lines(input, |line| parse_token(line, line, "integer")).map_err(|e| e.in_day(1))
+}
+
+/// Calls `found` with the positions in `sorted` of every combination of `k` values from
+/// `sorted[start..]` which sum to `target`, until it returns true.
+/// Returns true if `found` stopped the search.
+///
+/// The values are `i64`s widened to `i128`, so that sums of them don't overflow.
+fn k_sums(
+    sorted: &[i128],
+    start: usize,
+    k: usize,
+    target: i128,
+    chosen: &mut Vec<usize>,
+    found: &mut dyn FnMut(&[usize]) -> bool,
+) -> bool {
+    let rest = &sorted[start..];
+    if rest.len() < k {
+        return false;
+    }
+    // Skip the search if the target is out of reach of the smallest and the largest values
+    let min = rest[..k].iter().sum::<i128>();
+    let max = rest[rest.len() - k..].iter().sum::<i128>();
+    if target < min || target > max {
+        return false;
+    }
+    let mut emit = |positions: &[usize], chosen: &mut Vec<usize>| {
+        chosen.extend_from_slice(positions);
+        let stop = found(chosen);
+        chosen.truncate(chosen.len() - positions.len());
+        stop
+    };
+    match k {
+        0 => emit(&[], chosen),
+        1 => {
+            let first = start + rest.partition_point(|&value| value < target);
+            let end = start + rest.partition_point(|&value| value <= target);
+            (first..end).any(|i| emit(&[i], chosen))
+        }
+        2 => {
+            // Two pointers moving towards each other
+            let (mut low, mut high) = (start, sorted.len() - 1);
+            while low < high {
+                let sum = sorted[low] + sorted[high];
+                if sum < target {
+                    low += 1;
+                } else if sum > target {
+                    high -= 1;
+                } else if sorted[low] == sorted[high] {
+                    // All values in between are equal, so any two of them match
+                    return (low..high).any(|i| (i + 1..=high).any(|j| emit(&[i, j], chosen)));
+                } else {
+                    // Match every copy of the low value with every copy of the high value
+                    let low_end = low + sorted[low..].partition_point(|&v| v == sorted[low]);
+                    let high_start =
+                        low + sorted[low..=high].partition_point(|&v| v < sorted[high]);
+                    if (low..low_end).any(|i| (high_start..=high).any(|j| emit(&[i, j], chosen))) {
+                        return true;
+                    }
+                    low = low_end;
+                    high = high_start - 1;
+                }
+            }
+            false
+        }
+        _ => (start..sorted.len()).any(|i| {
+            chosen.push(i);
+            let stop = k_sums(sorted, i + 1, k - 1, target - sorted[i], chosen, found);
+            chosen.pop();
+            stop
+        }),
+    }
+}
+
+/// Searches combinations of `k` values which sum to `target`, calling `found` with the
+/// (ascending) indices of every combination until it returns true.
+fn search_k_sums<T>(values: &[T], k: usize, target: i64, mut found: impl FnMut(Vec<usize>) -> bool)
+where
+    T: Copy + Into<i64>,
+{
+    let mut order = (0..values.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| values[i].into());
+    let sorted = order
+        .iter()
+        .map(|&i| i128::from(values[i].into()))
+        .collect::<Vec<_>>();
+    k_sums(
+        &sorted,
+        0,
+        k,
+        target.into(),
+        &mut vec![],
+        &mut |positions| {
+            let mut indices = positions.iter().map(|&p| order[p]).collect::<Vec<_>>();
+            indices.sort_unstable();
+            found(indices)
+        },
+    );
+}
+
+/// Finds `k` different entries of `values` which sum to `target`, and returns their indices
+/// in ascending order, or `None` if there are none.
+///
+/// The values are sorted, and the last two terms are found with two pointers,
+/// which takes O(n^(k-1)) time for k >= 2.
+pub fn find_k_sum<T: Copy + Into<i64>>(values: &[T], k: usize, target: i64) -> Option<Vec<usize>> {
+    let mut solution = None;
+    search_k_sums(values, k, target, |indices| {
+        solution = Some(indices);
+        true
+    });
+    solution
+}
+
+/// Finds all sets of `k` different entries of `values` which sum to `target`, as lists of
+/// ascending indices, in ascending order. Equal values at different indices count as
+/// different entries.
+pub fn find_all_k_sums<T: Copy + Into<i64>>(
+    values: &[T],
+    k: usize,
+    target: i64,
+) -> Vec<Vec<usize>> {
+    let mut solutions = vec![];
+    search_k_sums(values, k, target, |indices| {
+        solutions.push(indices);
+        false
+    });
+    solutions.sort_unstable();
+    solutions
+}
+
+/// Returns the product of the `k` expenses which sum to 2020.
+fn product_of_k_sum(expenses: &[i32], k: usize) -> i32 {
+    let indices =
+        find_k_sum(expenses, k, 2020).unwrap_or_else(|| panic!("no {} expenses sum to 2020", k));
+    indices.iter().map(|&i| expenses[i]).product()
+}
+
+#[aoc(day1, part1)]
+pub fn part1(expenses: &[i32]) -> i32 {
+    product_of_k_sum(expenses, 2)
+}
+
+#[aoc(day1, part2)]
+pub fn part2(expenses: &[i32]) -> i32 {
+    product_of_k_sum(expenses, 3)
+}
+
+fn explain_k_sum(expenses: &[i32], k: usize) -> Option<Explanation> {
+    let indices = find_k_sum(expenses, k, 2020)?;
+    let terms = indices
+        .iter()
+        .map(|&i| expenses[i].to_string())
+        .collect::<Vec<_>>();
+    let product = indices.iter().map(|&i| expenses[i]).product::<i32>();
+    Some(Explanation {
+        answer: product.into(),
+        steps: vec![
+            format!("{} = 2020", terms.join(" + ")),
+            format!("{} = {}", terms.join(" * "), product),
+        ],
+        lines: indices
+            .into_iter()
+            .map(|i| (i, "term".to_owned()))
+            .collect(),
+    })
+}
+
+/// Explains a part: the expenses which sum to 2020, and their product.
+pub fn explain(input: &str, part: u8) -> Result<Explanation, Box<dyn Error>> {
+    let expenses = input_generator(input)?;
+    let k = match part {
+        1 => 2,
+        2 => 3,
+        part => return Err(format!("no part {}", part).into()),
+    };
+    Ok(explain_k_sum(&expenses, k).ok_or_else(|| format!("no {} expenses sum to 2020", k))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day1::{
+        explain, find_all_k_sums, find_k_sum, input_generator, part1, part2,
+    };
+    use crate::year2020::gen::Rng;
+    use crate::Answer;
+
+    const EXAMPLE: &str = "\
+1721
+979
+366
+299
+675
+1456";
+
+    #[test]
+    fn generator() {
+        assert_eq!(
+            input_generator(EXAMPLE).unwrap(),
+            vec![1721, 979, 366, 299, 675, 1456]
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 514579);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 241861950);
+    }
+
+    #[test]
+    fn k_sums() {
+        let values = input_generator(EXAMPLE).unwrap();
+        assert_eq!(find_k_sum(&values, 2, 2020), Some(vec![0, 3]));
+        assert_eq!(find_k_sum(&values, 3, 2020), Some(vec![1, 2, 4]));
+        assert_eq!(find_k_sum(&values, 4, 2020), None);
+        assert_eq!(find_k_sum(&values, 1, 366), Some(vec![2]));
+        assert_eq!(find_k_sum(&values, 0, 0), Some(vec![]));
+        assert_eq!(find_k_sum(&values, 7, 5496), None);
+        assert_eq!(find_k_sum(&values, 6, 5496), Some(vec![0, 1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn k_sums_near_limits() {
+        let values = [i64::MAX, i64::MAX - 1, i64::MIN, i64::MIN + 1, 0];
+        assert_eq!(find_k_sum(&values, 2, -1), Some(vec![0, 2]));
+        assert_eq!(find_k_sum(&values, 2, i64::MAX), Some(vec![0, 4]));
+        assert_eq!(find_k_sum(&values, 3, 0), Some(vec![0, 3, 4]));
+        assert_eq!(find_k_sum(&values, 2, i64::MIN), Some(vec![2, 4]));
+        assert_eq!(find_all_k_sums(&values, 4, -2), vec![vec![0, 1, 2, 3]]);
+    }
+
+    #[test]
+    fn all_k_sums() {
+        let values = [1, 1, 1, 3, 2, 2, -1];
+        assert_eq!(
+            find_all_k_sums(&values, 2, 2),
+            vec![vec![0, 1], vec![0, 2], vec![1, 2], vec![3, 6]]
+        );
+        assert_eq!(
+            find_all_k_sums(&values, 2, 3),
+            vec![
+                vec![0, 4],
+                vec![0, 5],
+                vec![1, 4],
+                vec![1, 5],
+                vec![2, 4],
+                vec![2, 5]
+            ]
+        );
+        assert_eq!(find_all_k_sums(&values, 3, 4).len(), 8);
+        assert!(find_all_k_sums(&values, 3, 100).is_empty());
+    }
+
+    #[test]
+    fn k_sums_match_brute_force() {
+        let mut rng = Rng::new(1);
+        let values = (0..24).map(|_| rng.between(-10, 10)).collect::<Vec<_>>();
+        for target in -12..=12 {
+            let mut pairs = vec![];
+            let mut triples = vec![];
+            for i in 0..values.len() {
+                for j in i + 1..values.len() {
+                    if values[i] + values[j] == target {
+                        pairs.push(vec![i, j]);
+                    }
+                    for k in j + 1..values.len() {
+                        if values[i] + values[j] + values[k] == target {
+                            triples.push(vec![i, j, k]);
+                        }
+                    }
+                }
+            }
+            assert_eq!(find_all_k_sums(&values, 2, target), pairs);
+            assert_eq!(find_all_k_sums(&values, 3, target), triples);
+        }
+    }
+
+    #[test]
+    fn explanation() {
+        let explanation = explain(EXAMPLE, 2).unwrap();
+        assert_eq!(explanation.answer, Answer::from(241861950));
+        assert_eq!(
+            explanation.steps,
+            vec!["979 + 366 + 675 = 2020", "979 * 366 * 675 = 241861950"]
+        );
+        let lines = explanation
+            .lines
+            .iter()
+            .map(|(line, _)| *line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![1, 2, 4]);
+        assert_eq!(
+            explain("1\n2", 1).unwrap_err().to_string(),
+            "no 2 expenses sum to 2020"
+        );
+        assert_eq!(explain(EXAMPLE, 3).unwrap_err().to_string(), "no part 3");
+    }
+}
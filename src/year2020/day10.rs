@@ -1,6 +1,9 @@
+use crate::util::{normalize, parse_lines, parse_token, ParseError};
+
 #[aoc_generator(day10)]
-pub fn input_generator(input: &str) -> Vec<u64> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<u64>, ParseError> {
+    let input = &normalize(input);
+    parse_lines(input, |line| parse_token(line, line, "integer")).map_err(|e| e.in_day(10))
 }
 
 fn get_joltages(input: &[u64]) -> Vec<u64> {
@@ -45,3 +48,72 @@ pub fn part2(input: &[u64]) -> u64 {
 
     ways_to_reach[output_joltage as usize]
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day10::{input_generator, part1, part2};
+
+    const EXAMPLE: &str = "\
+16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4";
+
+    const EXAMPLE_2: &str = "\
+28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+    #[test]
+    fn generator() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input, vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4]);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 7 * 5);
+        assert_eq!(part1(&input_generator(EXAMPLE_2).unwrap()), 22 * 10);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 8);
+        assert_eq!(part2(&input_generator(EXAMPLE_2).unwrap()), 19208);
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+use crate::util::{
+    grid_moore, line_of_sight, normalize, Automaton, Frames, Grid, NoTrace, Palette, ParseError,
+    Trace, TraceEvent, Vector2D,
+};
+use crate::Answer;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Tile {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Tile::Floor => '.',
+            Tile::Empty => 'L',
+            Tile::Occupied => '#',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[aoc_generator(day11)]
+pub fn input_generator(input: &str) -> Result<Grid<Tile>, ParseError> {
+    let input = &normalize(input);
+    Grid::parse(input, "`.`, `L` or `#`", |c| match c {
+        '.' => Some(Tile::Floor),
+        'L' => Some(Tile::Empty),
+        '#' => Some(Tile::Occupied),
+        _ => None,
+    })
+    .map_err(|e| e.in_day(11))
+}
+
+/// Returns the seating rules as an automaton, and the initially occupied seats.
+/// Floor positions never change, so only the seats take part in the automaton.
+fn seating<N>(
+    grid: &Grid<Tile>,
+    rule: &str,
+    neighbourhood: N,
+) -> (Automaton<Vector2D, N>, HashSet<Vector2D>)
+where
+    N: Fn(Vector2D) -> Vec<Vector2D>,
+{
+    let positions = |wanted: fn(Tile) -> bool| {
+        grid.iter()
+            .filter(move |&(_, &tile)| wanted(tile))
+            .map(|(pos, _)| pos)
+    };
+    let seats = positions(|tile| tile != Tile::Floor);
+    let automaton = Automaton::bounded(rule.parse().unwrap(), neighbourhood, seats);
+    (
+        automaton,
+        positions(|tile| tile == Tile::Occupied).collect(),
+    )
+}
+
+/// Runs the seating rules until nobody changes seats, and returns the number of occupied seats.
+fn count_occupied_when_stable<N>(
+    grid: &Grid<Tile>,
+    rule: &str,
+    neighbourhood: N,
+    trace: &mut impl Trace,
+) -> usize
+where
+    N: Fn(Vector2D) -> Vec<Vector2D>,
+{
+    let (automaton, mut occupied) = seating(grid, rule, neighbourhood);
+    for round in 1.. {
+        let next = automaton.step(&occupied);
+        if next == occupied {
+            break;
+        }
+        // Sort the flipped seats in row-major order, so the trace is the same on every run
+        let mut flipped = next.symmetric_difference(&occupied).collect::<Vec<_>>();
+        flipped.sort_by_key(|pos| (pos.y(), pos.x()));
+        for &pos in flipped {
+            trace.record(|| {
+                let seat = if next.contains(&pos) {
+                    "occupied"
+                } else {
+                    "empty"
+                };
+                TraceEvent::new("flip")
+                    .with("round", round)
+                    .with("x", pos.x())
+                    .with("y", pos.y())
+                    .with("seat", seat)
+            });
+        }
+        occupied = next;
+    }
+    occupied.len()
+}
+
+fn is_seat(tile: &Tile) -> bool {
+    *tile != Tile::Floor
+}
+
+const PART1_RULE: &str = "B0/S0123";
+const PART2_RULE: &str = "B0/S01234";
+
+#[aoc(day11, part1)]
+pub fn part1(input: &Grid<Tile>) -> usize {
+    // An empty seat with no occupied adjacent seats becomes occupied,
+    // an occupied seat with four or more occupied adjacent seats becomes empty.
+    count_occupied_when_stable(input, PART1_RULE, grid_moore(input, is_seat), &mut NoTrace)
+}
+
+#[aoc(day11, part2)]
+pub fn part2(input: &Grid<Tile>) -> usize {
+    // Same as part 1, but people look at the first seat in each direction,
+    // and it takes five or more occupied visible seats for an occupied seat to become empty.
+    count_occupied_when_stable(
+        input,
+        PART2_RULE,
+        line_of_sight(input, is_seat),
+        &mut NoTrace,
+    )
+}
+
+/// Runs a part with tracing: every seat which changes in a round is a `flip` event.
+pub fn trace(input: &str, part: u8, trace: &mut impl Trace) -> Result<Answer, Box<dyn Error>> {
+    let grid = input_generator(input)?;
+    let occupied = match part {
+        1 => count_occupied_when_stable(&grid, PART1_RULE, grid_moore(&grid, is_seat), trace),
+        2 => count_occupied_when_stable(&grid, PART2_RULE, line_of_sight(&grid, is_seat), trace),
+        part => return Err(format!("no part {}", part).into()),
+    };
+    Ok(occupied.into())
+}
+
+/// Writes a frame for every round until nobody changes seats: floor, empty seats and occupied
+/// seats are colors 0, 1 and 2 of the palette.
+fn render_rounds<N>(
+    grid: &Grid<Tile>,
+    rule: &str,
+    neighbourhood: N,
+    name: &str,
+    frames: &mut Frames,
+) -> io::Result<()>
+where
+    N: Fn(Vector2D) -> Vec<Vector2D>,
+{
+    let palette = Palette(vec![[48, 48, 48], [96, 176, 96], [224, 64, 64]]);
+    let (automaton, mut occupied) = seating(grid, rule, neighbourhood);
+    loop {
+        let frame = Grid::from_fn(grid.width(), grid.height(), |pos| {
+            match (grid[pos], occupied.contains(&pos)) {
+                (Tile::Floor, _) => 0,
+                (_, false) => 1,
+                (_, true) => 2,
+            }
+        });
+        frames.write(name, &frame, &palette)?;
+        let next = automaton.step(&occupied);
+        if next == occupied {
+            return Ok(());
+        }
+        occupied = next;
+    }
+}
+
+/// Renders the seat layout after every round, as `part1-NNNN` and `part2-NNNN`.
+pub fn render(input: &str, frames: &mut Frames) -> Result<(), Box<dyn Error>> {
+    let grid = input_generator(input)?;
+    render_rounds(
+        &grid,
+        PART1_RULE,
+        grid_moore(&grid, is_seat),
+        "part1",
+        frames,
+    )?;
+    render_rounds(
+        &grid,
+        PART2_RULE,
+        line_of_sight(&grid, is_seat),
+        "part2",
+        frames,
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::TraceEvent;
+    use crate::util::Vector2D;
+    use crate::year2020::day11::{input_generator, part1, part2, trace, Tile};
+    use crate::Answer;
+
+    const EXAMPLE: &str = "\
+L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+    #[test]
+    fn generator() {
+        let grid = input_generator(EXAMPLE).unwrap();
+        assert_eq!((grid.width(), grid.height()), (10, 10));
+        assert_eq!(grid[Vector2D::new(0, 0)], Tile::Empty);
+        assert_eq!(grid[Vector2D::new(1, 0)], Tile::Floor);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 37);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 26);
+    }
+
+    #[test]
+    fn traced_in_order() {
+        let trace_events = || {
+            let mut events = Vec::<TraceEvent>::new();
+            assert_eq!(trace(EXAMPLE, 2, &mut events).unwrap(), Answer::from(26));
+            events
+        };
+        assert_eq!(trace_events(), trace_events());
+    }
+}
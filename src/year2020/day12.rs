@@ -0,0 +1,222 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::util::{
+    integer, lines, normalize, parse, satisfy, NoTrace, ParseError, Parser, Trace, TraceEvent,
+    Vector2D,
+};
+use crate::Answer;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Orientation {
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Orientation {
+    fn step(&self) -> Vector2D {
+        match *self {
+            Orientation::North => Vector2D::new(0, 1),
+            Orientation::South => Vector2D::new(0, -1),
+            Orientation::West => Vector2D::new(-1, 0),
+            Orientation::East => Vector2D::new(1, 0),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Instruction {
+    Move(Orientation, i32),
+    Left(i32),
+    Right(i32),
+    Forward(i32),
+}
+
+/// Parses an instruction like `F10`.
+fn instruction<'a>() -> impl Parser<'a, Instruction> {
+    let action = satisfy("`N`, `S`, `W`, `E`, `L`, `R` or `F`", |c| {
+        "NSWELRF".contains(c)
+    });
+    let rotation = "a rotation by a multiple of 90 degrees";
+    action
+        .then(integer())
+        .try_map(rotation, |(action, value)| match action {
+            'L' | 'R' if value % 90 != 0 => None,
+            'N' => Some(Instruction::Move(Orientation::North, value)),
+            'S' => Some(Instruction::Move(Orientation::South, value)),
+            'W' => Some(Instruction::Move(Orientation::West, value)),
+            'E' => Some(Instruction::Move(Orientation::East, value)),
+            'L' => Some(Instruction::Left(value)),
+            'R' => Some(Instruction::Right(value)),
+            _ => Some(Instruction::Forward(value)),
+        })
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (action, value) = match *self {
+            Instruction::Move(Orientation::North, value) => ('N', value),
+            Instruction::Move(Orientation::South, value) => ('S', value),
+            Instruction::Move(Orientation::West, value) => ('W', value),
+            Instruction::Move(Orientation::East, value) => ('E', value),
+            Instruction::Left(value) => ('L', value),
+            Instruction::Right(value) => ('R', value),
+            Instruction::Forward(value) => ('F', value),
+        };
+        write!(f, "{}{}", action, value)
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, instruction())
+    }
+}
+
+#[aoc_generator(day12)]
+pub fn input_generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let input = &normalize(input);
+    parse(input, lines(instruction())).map_err(|e| e.in_day(12))
+}
+
+/// Returns the `move` event for the position of the ship after an instruction,
+/// with the direction the ship faces (part 1) or its waypoint (part 2).
+fn move_event(
+    instruction: &Instruction,
+    ship: Vector2D,
+    vector_name: &str,
+    vector: Vector2D,
+) -> TraceEvent {
+    TraceEvent::new("move")
+        .with("action", instruction)
+        .with("x", ship.x())
+        .with("y", ship.y())
+        .with(&format!("{}_x", vector_name), vector.x())
+        .with(&format!("{}_y", vector_name), vector.y())
+}
+
+fn run_part1(input: &[Instruction], trace: &mut impl Trace) -> i32 {
+    let mut pos = Vector2D::new(0, 0);
+    let mut orientation = Vector2D::new(1, 0);
+    for instruction in input {
+        match *instruction {
+            Instruction::Move(direction, steps) => {
+                pos += direction.step() * steps;
+            }
+            Instruction::Left(degrees) => {
+                orientation = rotate_left(orientation, degrees);
+            }
+            Instruction::Right(degrees) => {
+                orientation = rotate_left(orientation, -degrees);
+            }
+            Instruction::Forward(steps) => {
+                pos += orientation * steps;
+            }
+        }
+        trace.record(|| move_event(instruction, pos, "heading", orientation));
+    }
+    pos.manhattan_distance()
+}
+
+#[aoc(day12, part1)]
+pub fn part1(input: &[Instruction]) -> i32 {
+    run_part1(input, &mut NoTrace)
+}
+
+fn run_part2(input: &[Instruction], trace: &mut impl Trace) -> i32 {
+    let mut ship = Vector2D::new(0, 0);
+    let mut waypoint = Vector2D::new(10, 1);
+    for instruction in input {
+        match *instruction {
+            Instruction::Move(direction, steps) => {
+                waypoint += direction.step() * steps;
+            }
+            Instruction::Left(degrees) => {
+                waypoint = rotate_left(waypoint, degrees);
+            }
+            Instruction::Right(degrees) => {
+                waypoint = rotate_left(waypoint, -degrees);
+            }
+            Instruction::Forward(steps) => {
+                ship += waypoint * steps;
+            }
+        }
+        trace.record(|| move_event(instruction, ship, "waypoint", waypoint));
+    }
+    ship.manhattan_distance()
+}
+
+#[aoc(day12, part2)]
+pub fn part2(input: &[Instruction]) -> i32 {
+    run_part2(input, &mut NoTrace)
+}
+
+/// Runs a part with tracing: every instruction is a `move` event with the new position
+/// of the ship.
+pub fn trace(input: &str, part: u8, trace: &mut impl Trace) -> Result<Answer, Box<dyn Error>> {
+    let instructions = input_generator(input)?;
+    let distance = match part {
+        1 => run_part1(&instructions, trace),
+        2 => run_part2(&instructions, trace),
+        part => return Err(format!("no part {}", part).into()),
+    };
+    Ok(distance.into())
+}
+
+/// Rotates counterclockwise by a multiple of 90 degrees, which the parser ensures.
+fn rotate_left(pos: Vector2D, degrees: i32) -> Vector2D {
+    match degrees.rem_euclid(360) {
+        0 => pos,
+        90 => Vector2D::new(-pos.y(), pos.x()),
+        180 => Vector2D::new(-pos.x(), -pos.y()),
+        270 => Vector2D::new(pos.y(), -pos.x()),
+        _ => panic!("invalid degrees: {}", degrees),
+    }
+}
+
+/// The generator already rejects rotations which are not a multiple of 90 degrees, which are the only inputs the solutions can't handle.
+pub fn validate(input: &str) -> Result<Vec<String>, ParseError> {
+    input_generator(input).map(|_| vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day12::{input_generator, part1, part2, Instruction, Orientation};
+
+    const EXAMPLE: &str = "\
+F10
+N3
+F7
+R90
+F11";
+
+    #[test]
+    fn generator() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input.len(), 5);
+        assert!(matches!(input[0], Instruction::Forward(10)));
+        assert!(matches!(input[1], Instruction::Move(Orientation::North, 3)));
+        assert!(matches!(input[3], Instruction::Right(90)));
+        assert_eq!(
+            input_generator("F10\nR45").unwrap_err().to_string(),
+            "day 12, line 2, column 1: expected a rotation by a multiple of 90 degrees, found `R45`"
+        );
+        let input = input_generator("L450\nR360\nF1").unwrap();
+        assert_eq!((part1(&input), part2(&input)), (1, 11));
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 25);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 286);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::util::{crt, normalize, parse_token, ParseError};
+
+type Input = (i32, Vec<Option<i32>>);
+
+#[aoc_generator(day13)]
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let input = &normalize(input);
+    let mut lines = input.lines();
+    let earliest_time = lines.next().unwrap_or("");
+    let earliest_time =
+        parse_token::<i32>(earliest_time, earliest_time, "integer").map_err(|e| e.in_day(13))?;
+    let buses = lines.next().unwrap_or("");
+    let buses = buses
+        .split(',')
+        .map(|bus| {
+            if bus == "x" {
+                Ok(None)
+            } else {
+                parse_token(buses, bus, "bus ID or `x`").map(Some)
+            }
+        })
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.offset_lines(1).in_day(13))?;
+    Ok((earliest_time, buses))
+}
+
+#[aoc(day13, part1)]
+pub fn part1((earliest_time, buses): &Input) -> i32 {
+    let (next_bus, next_waiting_time) = buses
+        .iter()
+        .filter_map(|bus| *bus)
+        .map(|bus| {
+            let rem = earliest_time % bus;
+            let waiting_time = if rem == 0 { 0 } else { bus - rem };
+            (bus, waiting_time)
+        })
+        .min_by_key(|(_, waiting_time)| *waiting_time)
+        .unwrap();
+    next_bus * next_waiting_time
+}
+
+#[aoc(day13, part2, chinese_remainder_theorem)]
+pub fn part2_crt((_, buses): &Input) -> i64 {
+    // Solve equation system: x % bus[i] = waiting_time[i] (for all i)
+    let bus_times: Vec<(i64, i64)> = buses
+        .iter()
+        .enumerate()
+        .filter_map(|(offset, bus)| bus.map(|bus| (-(offset as i64), bus as i64)))
+        .collect();
+    let (timestamp, _) = crt(&bus_times).expect("no timestamp for these buses");
+    timestamp
+}
+
+#[aoc(day13, part2, iterative)]
+pub fn part2_iterative((_, buses): &Input) -> i64 {
+    let bus_offsets: Vec<(i64, i64)> = buses
+        .iter()
+        .enumerate()
+        .filter_map(|(offset, bus)| bus.map(|bus| (bus as i64, offset as i64)))
+        .collect();
+
+    let mut timestamp: i64 = 0;
+    let mut period: i64 = bus_offsets[0].0;
+    for (bus, offset) in bus_offsets.iter().skip(1) {
+        while (timestamp + offset) % bus != 0 {
+            timestamp += period;
+        }
+        period *= bus;
+    }
+    timestamp
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day13::{input_generator, part1, part2_crt, part2_iterative};
+
+    const EXAMPLE: &str = "\
+939
+7,13,x,x,59,x,31,19";
+
+    const PART2_EXAMPLES: &[(&str, i64)] = &[
+        ("7,13,x,x,59,x,31,19", 1068781),
+        ("17,x,13,19", 3417),
+        ("67,7,59,61", 754018),
+        ("67,x,7,59,61", 779210),
+        ("67,7,x,59,61", 1261476),
+        ("1789,37,47,1889", 1202161486),
+    ];
+
+    #[test]
+    fn generator() {
+        let (earliest_time, buses) = input_generator(EXAMPLE).unwrap();
+        assert_eq!(earliest_time, 939);
+        assert_eq!(
+            buses,
+            vec![
+                Some(7),
+                Some(13),
+                None,
+                None,
+                Some(59),
+                None,
+                Some(31),
+                Some(19)
+            ]
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 295);
+    }
+
+    #[test]
+    fn part2_example() {
+        for &(buses, expected) in PART2_EXAMPLES {
+            let input = input_generator(&format!("0\n{}", buses)).unwrap();
+            assert_eq!(part2_crt(&input), expected);
+            assert_eq!(part2_iterative(&input), expected);
+        }
+    }
+}
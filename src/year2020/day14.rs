@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::util::{
+    integer, lines, literal, normalize, parse, preceded, take_while1, NoTrace, ParseError, Parser,
+    Trace, TraceEvent,
+};
+use crate::Answer;
+
+const SIZE: usize = 36;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MaskBit {
+    ZERO,
+    ONE,
+    FLOATING,
+}
+
+impl MaskBit {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '0' => Some(MaskBit::ZERO),
+            '1' => Some(MaskBit::ONE),
+            'X' => Some(MaskBit::FLOATING),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Mask {
+    bits: [MaskBit; SIZE],
+}
+
+/// Parses the 36 bits of a mask like `XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X`.
+fn mask<'a>() -> impl Parser<'a, Mask> {
+    take_while1("`0`, `1` or `X`", |c| MaskBit::from_char(c).is_some()).try_map(
+        format!("mask of {} bits", SIZE),
+        |s| {
+            let bits = s.chars().filter_map(MaskBit::from_char).collect::<Vec<_>>();
+            Some(Mask {
+                bits: bits.try_into().ok()?,
+            })
+        },
+    )
+}
+
+impl Display for Mask {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for bit in &self.bits {
+            let c = match bit {
+                MaskBit::ZERO => '0',
+                MaskBit::ONE => '1',
+                MaskBit::FLOATING => 'X',
+            };
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Mask {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, mask())
+    }
+}
+
+fn to_binary_digits(num: u64) -> [u8; SIZE] {
+    (0..SIZE)
+        .rev()
+        .map(|shift| if num & (1 << shift) != 0 { 1 } else { 0 })
+        .collect::<Vec<u8>>()
+        .try_into()
+        .unwrap()
+}
+
+fn from_binary_digits(digits: [u8; SIZE]) -> u64 {
+    digits
+        .iter()
+        .fold(0u64, |acc, digit| (acc << 1) | ((*digit != 0) as u64))
+}
+
+impl Mask {
+    fn new() -> Self {
+        Mask {
+            bits: [MaskBit::ZERO; SIZE],
+        }
+    }
+
+    fn get_mask(&self, filter_bit: MaskBit) -> u64 {
+        self.bits.iter().enumerate().fold(0u64, |acc, (i, bit)| {
+            if bit == &filter_bit {
+                acc | (1 << ((SIZE - 1) - i))
+            } else {
+                acc
+            }
+        })
+    }
+
+    fn zero_mask(&self) -> u64 {
+        self.get_mask(MaskBit::ZERO)
+    }
+
+    fn ones_mask(&self) -> u64 {
+        self.get_mask(MaskBit::ONE)
+    }
+
+    fn get_value_part1(&self, mut value: u64) -> u64 {
+        value |= self.ones_mask();
+        value &= !self.zero_mask();
+        value
+    }
+
+    fn get_addresses_part2(&self, input_address: u64) -> Vec<u64> {
+        let mut addresses = Vec::<[u8; SIZE]>::new();
+        addresses.push(to_binary_digits(input_address));
+        for i in 0..SIZE {
+            match self.bits[i] {
+                MaskBit::ZERO => {
+                    // do nothing
+                }
+                MaskBit::ONE => {
+                    for address in addresses.iter_mut() {
+                        address[i] = 1;
+                    }
+                }
+                MaskBit::FLOATING => {
+                    let mut with_ones = addresses.clone();
+                    for address in addresses.iter_mut() {
+                        address[i] = 0;
+                    }
+                    for address in with_ones.iter_mut() {
+                        address[i] = 1;
+                    }
+                    addresses.extend(with_ones);
+                }
+            };
+        }
+        addresses.into_iter().map(from_binary_digits).collect()
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum Instruction {
+    Mask { mask: Mask },
+    Memory { address: u64, value: u64 },
+}
+
+/// Parses an instruction like `mask = <bits>` or `mem[8] = 11`.
+fn instruction<'a>() -> impl Parser<'a, Instruction> {
+    let set_mask = preceded(literal("mask = "), mask()).map(|mask| Instruction::Mask { mask });
+    let write = preceded(literal("mem["), integer())
+        .skip(literal("] = "))
+        .then(integer())
+        .map(|(address, value)| Instruction::Memory { address, value });
+    set_mask.or(write)
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, instruction())
+    }
+}
+
+#[derive(Debug)]
+struct Machine {
+    program: Vec<Instruction>,
+    pc: usize,
+    memory: HashMap<u64, u64>,
+    mask: Mask,
+}
+
+impl Machine {
+    fn new(program: Vec<Instruction>) -> Self {
+        Self {
+            program,
+            pc: 0,
+            memory: HashMap::new(),
+            mask: Mask::new(),
+        }
+    }
+
+    fn set_mask(&mut self, mask: Mask, trace: &mut impl Trace) {
+        self.mask = mask;
+        trace.record(|| TraceEvent::new("mask").with("mask", mask));
+    }
+
+    fn write(&mut self, address: u64, value: u64, trace: &mut impl Trace) {
+        self.memory.insert(address, value);
+        trace.record(|| {
+            TraceEvent::new("write")
+                .with("pc", self.pc)
+                .with("address", address)
+                .with("value", value)
+        });
+    }
+
+    fn step_part1(&mut self, trace: &mut impl Trace) -> Option<()> {
+        if self.pc >= self.program.len() {
+            return None;
+        }
+        match self.program[self.pc] {
+            Instruction::Mask { mask } => {
+                self.set_mask(mask, trace);
+            }
+            Instruction::Memory { address, value } => {
+                let value = self.mask.get_value_part1(value);
+                self.write(address, value, trace);
+            }
+        };
+        self.pc += 1;
+        Some(())
+    }
+
+    fn step_part2(&mut self, trace: &mut impl Trace) -> Option<()> {
+        if self.pc >= self.program.len() {
+            return None;
+        }
+        match self.program[self.pc] {
+            Instruction::Mask { mask } => {
+                self.set_mask(mask, trace);
+            }
+            Instruction::Memory { address, value } => {
+                let addresses = self.mask.get_addresses_part2(address);
+                for address in addresses {
+                    self.write(address, value, trace);
+                }
+            }
+        };
+        self.pc += 1;
+        Some(())
+    }
+}
+
+#[aoc_generator(day14)]
+pub fn input_generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let input = &normalize(input);
+    parse(input, lines(instruction())).map_err(|e| e.in_day(14))
+}
+
+fn run_part1(input: &[Instruction], trace: &mut impl Trace) -> u64 {
+    let mut machine = Machine::new(input.to_vec());
+    while machine.step_part1(trace).is_some() {}
+    machine.memory.values().sum()
+}
+
+#[aoc(day14, part1)]
+pub fn part1(input: &[Instruction]) -> u64 {
+    run_part1(input, &mut NoTrace)
+}
+
+fn run_part2(input: &[Instruction], trace: &mut impl Trace) -> u64 {
+    let mut machine = Machine::new(input.to_vec());
+    while machine.step_part2(trace).is_some() {}
+    machine.memory.values().sum()
+}
+
+#[aoc(day14, part2)]
+pub fn part2(input: &[Instruction]) -> u64 {
+    run_part2(input, &mut NoTrace)
+}
+
+/// Runs a part with tracing: every new mask is a `mask` event, and every value written
+/// to memory is a `write` event.
+pub fn trace(input: &str, part: u8, trace: &mut impl Trace) -> Result<Answer, Box<dyn Error>> {
+    let program = input_generator(input)?;
+    let sum = match part {
+        1 => run_part1(&program, trace),
+        2 => run_part2(&program, trace),
+        part => return Err(format!("no part {}", part).into()),
+    };
+    Ok(sum.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day14::{input_generator, part1, part2, Instruction};
+
+    const EXAMPLE: &str = "\
+mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+
+    const EXAMPLE_2: &str = "\
+mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+    #[test]
+    fn generator() {
+        let program = input_generator(EXAMPLE).unwrap();
+        assert_eq!(program.len(), 4);
+        assert!(matches!(program[0], Instruction::Mask { .. }));
+        assert!(matches!(
+            program[2],
+            Instruction::Memory {
+                address: 7,
+                value: 101
+            }
+        ));
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 165);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE_2).unwrap()), 208);
+    }
+}
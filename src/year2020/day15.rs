@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use crate::util::{normalize, parse_token, ParseError};
+
+#[aoc_generator(day15)]
+pub fn input_generator(input: &str) -> Result<Vec<i32>, ParseError> {
+    let input = &normalize(input);
+    input
+        .split(',')
+        .map(|num| parse_token(input, num, "integer"))
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.in_day(15))
+}
+
+fn solve(input: &[i32], limit: usize) -> i32 {
+    let mut last_seen = HashMap::<i32, usize>::new();
+    let (&last, rest) = input.split_last().unwrap();
+    for (i, num) in rest.iter().enumerate() {
+        last_seen.insert(*num, i);
+    }
+    let mut last = last;
+    for i in input.len()..limit {
+        let prev = last_seen.get(&last);
+        let next = match prev {
+            Some(prev) => ((i - 1) - prev) as i32,
+            None => 0,
+        };
+        last_seen.insert(last, i - 1);
+        last = next;
+    }
+    last
+}
+
+#[aoc(day15, part1)]
+pub fn part1(input: &[i32]) -> i32 {
+    solve(input, 2020)
+}
+
+#[aoc(day15, part2)]
+pub fn part2(input: &[i32]) -> i32 {
+    solve(input, 30_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day15::{input_generator, part1, part2};
+
+    const EXAMPLES: &[(&str, i32, i32)] = &[
+        ("0,3,6", 436, 175594),
+        ("1,3,2", 1, 2578),
+        ("2,1,3", 10, 3544142),
+        ("1,2,3", 27, 261214),
+        ("2,3,1", 78, 6895259),
+        ("3,2,1", 438, 18),
+        ("3,1,2", 1836, 362),
+    ];
+
+    #[test]
+    fn generator() {
+        assert_eq!(input_generator("0,3,6").unwrap(), vec![0, 3, 6]);
+    }
+
+    #[test]
+    fn part1_example() {
+        for &(input, expected, _) in EXAMPLES {
+            assert_eq!(part1(&input_generator(input).unwrap()), expected);
+        }
+    }
+
+    /// Takes 30 million turns, run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn part2_example() {
+        for &(input, _, expected) in EXAMPLES {
+            assert_eq!(part2(&input_generator(input).unwrap()), expected);
+        }
+    }
+}
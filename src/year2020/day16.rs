@@ -0,0 +1,266 @@
+use std::collections::HashSet;
+
+use crate::util::{
+    integer, lines, literal, normalize, parse, section, separated, take_while1, ParseError, Parser,
+};
+
+pub type Ticket = Vec<i32>;
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    name: String,
+    ranges: Vec<(i32, i32)>,
+}
+
+impl Field {
+    fn is_valid(&self, value: i32) -> bool {
+        self.ranges
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&value))
+    }
+}
+
+#[derive(Debug)]
+pub struct Input {
+    fields: Vec<Field>,
+    my_ticket: Ticket,
+    nearby_tickets: Vec<Ticket>,
+}
+
+/// Parses a field rule like `departure location: 25-80 or 90-961`.
+fn field<'a>() -> impl Parser<'a, Field> {
+    let name = take_while1("field name", |c| c != ':' && c != '\n');
+    let range = integer().skip(literal("-")).then(integer());
+    name.skip(literal(": "))
+        .then(separated(range, literal(" or ")))
+        .map(|(name, ranges)| Field {
+            name: name.to_owned(),
+            ranges,
+        })
+}
+
+fn ticket<'a>() -> impl Parser<'a, Ticket> {
+    separated(integer(), literal(","))
+}
+
+#[aoc_generator(day16)]
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let input = &normalize(input);
+    let parser = lines(field())
+        .skip(literal("\n\n"))
+        .then(section("your ticket:", ticket()))
+        .skip(literal("\n\n"))
+        .then(section("nearby tickets:", lines(ticket())))
+        .map(|((fields, my_ticket), nearby_tickets)| Input {
+            fields,
+            my_ticket,
+            nearby_tickets,
+        });
+    parse(input, parser).map_err(|e| e.in_day(16))
+}
+
+#[aoc(day16, part1)]
+pub fn part1(input: &Input) -> i32 {
+    let invalid_values = input.nearby_tickets.iter().flat_map(|ticket| {
+        ticket
+            .iter()
+            .filter(|&&value| !input.fields.iter().any(|field| field.is_valid(value)))
+    });
+    invalid_values.sum()
+}
+
+/// Assigns every field to the only ticket position where all of its values are valid,
+/// one field at a time. Returns `None` if at some point no field has a single candidate.
+fn assign_fields(fields: &[Field], tickets: &[Ticket]) -> Option<Vec<(Field, usize)>> {
+    let mut assigned = Vec::<(Field, usize)>::new();
+    let mut fields = fields.to_vec();
+    let mut indexes = (0..tickets[0].len()).collect::<Vec<usize>>();
+    assert_eq!(fields.len(), indexes.len());
+    'outer: while !fields.is_empty() {
+        for (i, field) in fields.iter().enumerate() {
+            let candidate_indexes = indexes
+                .iter()
+                .filter(|&&index| tickets.iter().all(|ticket| field.is_valid(ticket[index])))
+                .cloned()
+                .collect::<Vec<_>>();
+            if let [field_index] = candidate_indexes[..] {
+                // only one candidate index for this field, assign it
+                assigned.push((field.clone(), field_index));
+                fields.remove(i);
+                indexes.remove(indexes.iter().position(|&x| x == field_index).unwrap());
+                continue 'outer;
+            }
+        }
+        return None;
+    }
+    Some(assigned)
+}
+
+fn valid_tickets(input: &Input) -> Vec<Ticket> {
+    input
+        .nearby_tickets
+        .iter()
+        .filter(|ticket| {
+            ticket
+                .iter()
+                .all(|&value| input.fields.iter().any(|field| field.is_valid(value)))
+        })
+        .cloned()
+        .collect()
+}
+
+#[aoc(day16, part2)]
+pub fn part2(input: &Input) -> i64 {
+    let assignments =
+        assign_fields(&input.fields, &valid_tickets(input)).expect("could not assign field!");
+    assignments
+        .into_iter()
+        .filter(|(field, _)| field.name.starts_with("departure"))
+        .map(|(_, index)| input.my_ticket[index] as i64)
+        .product()
+}
+
+/// Checks that field names are unique, that all tickets have a value for every field, and that
+/// the fields can be assigned to ticket positions.
+pub fn validate(input: &str) -> Result<Vec<String>, ParseError> {
+    let input = input_generator(input)?;
+    let mut violations = vec![];
+    let mut names = HashSet::new();
+    for field in &input.fields {
+        if !names.insert(&field.name) {
+            violations.push(format!("field `{}` is defined twice", field.name));
+        }
+    }
+    let width = input.fields.len();
+    if input.my_ticket.len() != width {
+        violations.push(format!(
+            "your ticket has {} values, expected {} (one per field)",
+            input.my_ticket.len(),
+            width
+        ));
+    }
+    for (i, ticket) in input.nearby_tickets.iter().enumerate() {
+        if ticket.len() != width {
+            violations.push(format!(
+                "nearby ticket {} has {} values, expected {} (one per field)",
+                i + 1,
+                ticket.len(),
+                width
+            ));
+        }
+    }
+    if !violations.is_empty() {
+        return Ok(violations);
+    }
+    let tickets = valid_tickets(&input);
+    if tickets.is_empty() {
+        violations.push("no nearby ticket is valid".to_owned());
+    } else if assign_fields(&input.fields, &tickets).is_none() {
+        violations.push(
+            "the fields cannot be assigned to ticket positions one at a time, \
+             because no field fits exactly one of the remaining positions"
+                .to_owned(),
+        );
+    }
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day16::{assign_fields, input_generator, part1, validate};
+
+    const EXAMPLE: &str = "\
+class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+
+    const EXAMPLE_2: &str = "\
+class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+
+    #[test]
+    fn generator() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input.fields.len(), 3);
+        assert_eq!(input.fields[1].name, "row");
+        assert_eq!(input.fields[1].ranges, vec![(6, 11), (33, 44)]);
+        assert_eq!(input.my_ticket, vec![7, 1, 14]);
+        assert_eq!(input.nearby_tickets.len(), 4);
+    }
+
+    #[test]
+    fn generator_errors() {
+        let two_tickets = EXAMPLE.replace("7,1,14\n", "7,1,14\n1,2,3\n");
+        let error = input_generator(&two_tickets).unwrap_err();
+        assert_eq!((error.line(), error.column()), (Some(6), Some(7)));
+        let bad_range = EXAMPLE.replace("6-11", "6..11");
+        let error = input_generator(&bad_range).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 16, line 2, column 7: expected `-`, found `.`"
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 71);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = input_generator(EXAMPLE_2).unwrap();
+        let mut assignments = assign_fields(&input.fields, &input.nearby_tickets)
+            .unwrap()
+            .into_iter()
+            .map(|(field, index)| (field.name, input.my_ticket[index]))
+            .collect::<Vec<_>>();
+        assignments.sort();
+        assert_eq!(
+            assignments,
+            vec![
+                ("class".to_owned(), 12),
+                ("row".to_owned(), 11),
+                ("seat".to_owned(), 13)
+            ]
+        );
+    }
+
+    #[test]
+    fn validation() {
+        assert!(validate(EXAMPLE_2).unwrap().is_empty());
+        let widths = EXAMPLE_2
+            .replace("15,1,5", "15,1")
+            .replace("11,12,13", "11,12,13,14");
+        assert_eq!(
+            validate(&widths).unwrap(),
+            vec![
+                "your ticket has 4 values, expected 3 (one per field)",
+                "nearby ticket 2 has 2 values, expected 3 (one per field)",
+            ]
+        );
+        // Any field fits any position
+        let ambiguous = EXAMPLE_2
+            .replace("0-13", "0-19")
+            .replace("0-5", "0-19")
+            .replace("0-1 ", "0-19 ");
+        assert_eq!(validate(&ambiguous).unwrap().len(), 1);
+    }
+}
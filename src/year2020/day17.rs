@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::io;
+
+use crate::util::{moore, normalize, Automaton, Frames, Grid, Palette, ParseError, VectorN};
+
+/// The active cubes of a pocket dimension with `D` dimensions.
+#[derive(Debug, Clone)]
+pub struct PocketDimension<const D: usize> {
+    cubes: HashSet<VectorN<D>>,
+}
+
+impl<const D: usize> PocketDimension<D> {
+    /// Embeds the pocket dimension in a pocket dimension with `E` dimensions,
+    /// with all extra coordinates set to 0.
+    pub fn resize<const E: usize>(&self) -> PocketDimension<E> {
+        let cubes = self.cubes.iter().map(|pos| pos.resize()).collect();
+        PocketDimension { cubes }
+    }
+}
+
+/// Embeds the initial slice in `D` dimensions, and returns the number of active cubes
+/// after the given number of cycles.
+pub fn simulate<const D: usize>(input: &PocketDimension<2>, cycles: usize) -> usize {
+    automaton::<D>()
+        .run(&input.resize::<D>().cubes, cycles)
+        .len()
+}
+
+fn automaton<const D: usize>() -> Automaton<VectorN<D>, impl Fn(VectorN<D>) -> Vec<VectorN<D>>> {
+    // If a cube is active and exactly 2 or 3 of its neighbors are also active,
+    // the cube remains active. If a cube is inactive but exactly 3 of its neighbors
+    // are active, the cube becomes active. Otherwise, the cube remains inactive.
+    Automaton::new("B3/S23".parse().unwrap(), moore::<D>())
+}
+
+#[aoc_generator(day17)]
+pub fn input_generator(input: &str) -> Result<PocketDimension<2>, ParseError> {
+    let input = &normalize(input);
+    let mut cubes = HashSet::new();
+    for (y, line) in input.lines().enumerate() {
+        for (x, (i, char)) in line.char_indices().enumerate() {
+            match char {
+                '#' => {
+                    cubes.insert(VectorN::<2>::new(x as i32, y as i32));
+                }
+                '.' => {}
+                _ => {
+                    return Err(ParseError::at_char(line, i, "`#` or `.`")
+                        .offset_lines(y)
+                        .in_day(17))
+                }
+            }
+        }
+    }
+    Ok(PocketDimension { cubes })
+}
+
+#[aoc(day17, part1)]
+pub fn part1(input: &PocketDimension<2>) -> usize {
+    simulate::<3>(input, 6)
+}
+
+#[aoc(day17, part2)]
+pub fn part2(input: &PocketDimension<2>) -> usize {
+    simulate::<4>(input, 6)
+}
+
+/// Writes a frame for every cycle, with all x/y slices side by side: z increases to the right,
+/// and w (in 4 dimensions) increases downwards. Gaps between the slices, inactive cubes and
+/// active cubes are colors 0, 1 and 2 of the palette.
+fn render_cycles<const D: usize>(
+    input: &PocketDimension<2>,
+    cycles: usize,
+    name: &str,
+    frames: &mut Frames,
+) -> io::Result<()> {
+    let automaton = automaton::<D>();
+    let mut states = vec![input.resize::<D>().cubes];
+    for cycle in 0..cycles {
+        states.push(automaton.step(&states[cycle]));
+    }
+
+    // Use the same bounds for all frames
+    let mut min = VectorN::<D>::zero();
+    let mut max = VectorN::<D>::zero();
+    for cube in states.iter().flatten() {
+        for axis in 0..D {
+            min[axis] = min[axis].min(cube[axis]);
+            max[axis] = max[axis].max(cube[axis]);
+        }
+    }
+    let extent = |axis: usize| {
+        if axis < D {
+            (max[axis] - min[axis] + 1) as usize
+        } else {
+            1
+        }
+    };
+    let (width, height) = (extent(0), extent(1));
+    let frame_width = extent(2) * (width + 1) - 1;
+    let frame_height = extent(3) * (height + 1) - 1;
+
+    let palette = Palette(vec![[16, 16, 32], [40, 40, 64], [96, 224, 255]]);
+    for state in &states {
+        let frame = Grid::from_fn(frame_width, frame_height, |pos| {
+            let (x, y) = (pos.x() as usize, pos.y() as usize);
+            if x % (width + 1) == width || y % (height + 1) == height {
+                return 0;
+            }
+            let mut cube = min;
+            cube[0] += (x % (width + 1)) as i32;
+            cube[1] += (y % (height + 1)) as i32;
+            if D > 2 {
+                cube[2] += (x / (width + 1)) as i32;
+            }
+            if D > 3 {
+                cube[3] += (y / (height + 1)) as i32;
+            }
+            if state.contains(&cube) {
+                2
+            } else {
+                1
+            }
+        });
+        frames.write(name, &frame, &palette)?;
+    }
+    Ok(())
+}
+
+/// Renders all slices after every cycle, as `part1-NNNN` (3 dimensions)
+/// and `part2-NNNN` (4 dimensions).
+pub fn render(input: &str, frames: &mut Frames) -> Result<(), Box<dyn Error>> {
+    let input = input_generator(input)?;
+    render_cycles::<3>(&input, 6, "part1", frames)?;
+    render_cycles::<4>(&input, 6, "part2", frames)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day17::{input_generator, part1, part2, simulate};
+
+    const EXAMPLE: &str = "\
+.#.
+..#
+###";
+
+    #[test]
+    fn generator() {
+        assert_eq!(input_generator(EXAMPLE).unwrap().cubes.len(), 5);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 112);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 848);
+    }
+
+    #[test]
+    fn glider() {
+        // In 2 dimensions, the example is a glider from Conway's Game of Life
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(simulate::<2>(&input, 4), 5);
+        assert_eq!(simulate::<2>(&input, 100), 5);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::util::{normalize, parse_lines, parse_token, ParseError};
+
+#[derive(Copy, Clone)]
+enum Part {
+    Part1,
+    Part2,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Token {
+    Lit(i64),
+    Add,
+    Mul,
+    OpenParen,
+    CloseParen,
+}
+
+#[derive(Debug)]
+pub enum Expression {
+    Lit(i64),
+    Add(Box<Expression>, Box<Expression>),
+    Mul(Box<Expression>, Box<Expression>),
+}
+
+fn lex(s: &str) -> Result<Vec<Token>, ParseError> {
+    let mut result = vec![];
+    let mut chars = s.char_indices().peekable();
+    while let Some((start, char)) = chars.next() {
+        if char.is_ascii_whitespace() {
+            continue;
+        } else if char.is_ascii_digit() {
+            let mut end = start + 1;
+            while let Some(&(i, char)) = chars.peek() {
+                if char.is_ascii_digit() {
+                    chars.next();
+                    end = i + 1;
+                } else {
+                    break;
+                }
+            }
+            let text = &s[start..end];
+            result.push((Token::Lit(parse_token(s, text, "integer")?), text));
+        } else {
+            let token = match char {
+                '(' => Token::OpenParen,
+                ')' => Token::CloseParen,
+                '+' => Token::Add,
+                '*' => Token::Mul,
+                _ => {
+                    return Err(ParseError::at_char(
+                        s,
+                        start,
+                        "integer, `(`, `)`, `+` or `*`",
+                    ))
+                }
+            };
+            result.push((token, &s[start..start + 1]));
+        }
+    }
+    check_syntax(s, &result)?;
+    Ok(result.into_iter().map(|(token, _)| token).collect())
+}
+
+/// Checks that operators are between operands, and that parentheses are balanced,
+/// so that the expression can be parsed with either precedence.
+fn check_syntax(s: &str, tokens: &[(Token, &str)]) -> Result<(), ParseError> {
+    let mut expect_operand = true;
+    let mut depth = 0;
+    for &(token, text) in tokens {
+        match (expect_operand, token) {
+            (true, Token::Lit(_)) => expect_operand = false,
+            (true, Token::OpenParen) => depth += 1,
+            (true, _) => return Err(ParseError::at(s, text, "integer or `(`")),
+            (false, Token::Add) | (false, Token::Mul) => expect_operand = true,
+            (false, Token::CloseParen) if depth > 0 => depth -= 1,
+            (false, _) if depth > 0 => return Err(ParseError::at(s, text, "`+`, `*` or `)`")),
+            (false, _) => return Err(ParseError::at(s, text, "`+` or `*`")),
+        }
+    }
+    let end = &s[s.len()..];
+    if expect_operand {
+        return Err(ParseError::at(s, end, "integer or `(`"));
+    }
+    if depth > 0 {
+        return Err(ParseError::at(s, end, "`)`"));
+    }
+    Ok(())
+}
+
+fn consume_token(tokens: &[Token], expected: Token) -> &[Token] {
+    let (first, rest) = tokens.split_first().unwrap();
+    assert_eq!(first, &expected);
+    rest
+}
+
+fn parse_number_or_parens(tokens: &[Token], part: Part) -> Option<(Expression, &[Token])> {
+    match tokens.split_first() {
+        Some((Token::OpenParen, rest)) => {
+            let (expr, rest) = parse_expression(rest, part)?;
+            let rest = consume_token(rest, Token::CloseParen);
+            Some((expr, rest))
+        }
+        Some((Token::Lit(num), rest)) => {
+            let expr = Expression::Lit(*num);
+            Some((expr, rest))
+        }
+        _ => None,
+    }
+}
+
+fn parse_expression_part1(tokens: &[Token]) -> Option<(Expression, &[Token])> {
+    let (mut expr, mut tokens) = parse_number_or_parens(tokens, Part::Part1)?;
+    while let Some((token, rest)) = tokens.split_first() {
+        match token {
+            Token::Add => {
+                let (rhs_expr, rest) = parse_number_or_parens(rest, Part::Part1)?;
+                expr = Expression::Add(Box::new(expr), Box::new(rhs_expr));
+                tokens = rest
+            }
+            Token::Mul => {
+                let (rhs_expr, rest) = parse_number_or_parens(rest, Part::Part1)?;
+                expr = Expression::Mul(Box::new(expr), Box::new(rhs_expr));
+                tokens = rest;
+            }
+            _ => break,
+        }
+    }
+    Some((expr, tokens))
+}
+
+fn parse_term_part2(tokens: &[Token]) -> Option<(Expression, &[Token])> {
+    let (mut expr, mut tokens) = parse_number_or_parens(tokens, Part::Part2)?;
+    while let Some((Token::Add, rest)) = tokens.split_first() {
+        let (rhs_expr, rest) = parse_number_or_parens(rest, Part::Part2)?;
+        expr = Expression::Add(Box::new(expr), Box::new(rhs_expr));
+        tokens = rest
+    }
+    Some((expr, tokens))
+}
+
+fn parse_expression_part2(tokens: &[Token]) -> Option<(Expression, &[Token])> {
+    let (mut expr, mut tokens) = parse_term_part2(tokens)?;
+    while let Some((Token::Mul, rest)) = tokens.split_first() {
+        let (rhs_expr, rest) = parse_term_part2(rest)?;
+        expr = Expression::Mul(Box::new(expr), Box::new(rhs_expr));
+        tokens = rest;
+    }
+    Some((expr, tokens))
+}
+
+fn parse_expression(tokens: &[Token], part: Part) -> Option<(Expression, &[Token])> {
+    match part {
+        Part::Part1 => parse_expression_part1(tokens),
+        Part::Part2 => parse_expression_part2(tokens),
+    }
+}
+
+fn parse_full(tokens: &[Token], part: Part) -> Option<Expression> {
+    let (expr, rest) = parse_expression(tokens, part)?;
+    assert!(rest.is_empty());
+    Some(expr)
+}
+
+fn eval(expr: &Expression) -> i64 {
+    match expr {
+        Expression::Lit(num) => *num,
+        Expression::Add(lhs, rhs) => eval(lhs) + eval(rhs),
+        Expression::Mul(lhs, rhs) => eval(lhs) * eval(rhs),
+    }
+}
+
+#[aoc_generator(day18)]
+pub fn input_generator(input: &str) -> Result<Vec<Vec<Token>>, ParseError> {
+    let input = &normalize(input);
+    parse_lines(input, lex).map_err(|e| e.in_day(18))
+}
+
+#[aoc(day18, part1)]
+pub fn part1(input: &[Vec<Token>]) -> i64 {
+    input
+        .iter()
+        .map(|tokens| eval(&parse_full(tokens, Part::Part1).unwrap()))
+        .sum()
+}
+
+#[aoc(day18, part2)]
+pub fn part2(input: &[Vec<Token>]) -> i64 {
+    input
+        .iter()
+        .map(|tokens| eval(&parse_full(tokens, Part::Part2).unwrap()))
+        .sum()
+}
+
+/// The generator already rejects unbalanced parentheses and misplaced operators, which are the only inputs the solutions can't handle.
+pub fn validate(input: &str) -> Result<Vec<String>, ParseError> {
+    input_generator(input).map(|_| vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day18::{input_generator, part1, part2, Token};
+
+    const EXAMPLES: &[(&str, i64, i64)] = &[
+        ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+        ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+        ("2 * 3 + (4 * 5)", 26, 46),
+        ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+        ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+        (
+            "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+            13632,
+            23340,
+        ),
+    ];
+
+    #[test]
+    fn generator() {
+        assert_eq!(
+            input_generator("2 * (34 + 5)").unwrap(),
+            vec![vec![
+                Token::Lit(2),
+                Token::Mul,
+                Token::OpenParen,
+                Token::Lit(34),
+                Token::Add,
+                Token::Lit(5),
+                Token::CloseParen
+            ]]
+        );
+    }
+
+    #[test]
+    fn malformed_expressions() {
+        let error = |input| input_generator(input).unwrap_err().to_string();
+        assert_eq!(
+            error("1 + (2"),
+            "day 18, line 1, column 7: expected `)`, found end of input"
+        );
+        assert_eq!(
+            error("1 + 2)"),
+            "day 18, line 1, column 6: expected `+` or `*`, found `)`"
+        );
+        assert_eq!(
+            error("1\n(2 * + 3)"),
+            "day 18, line 2, column 6: expected integer or `(`, found `+`"
+        );
+        assert_eq!(
+            error("(1 2)"),
+            "day 18, line 1, column 4: expected `+`, `*` or `)`, found `2`"
+        );
+        assert!(error("1\n\n2").starts_with("day 18, line 2"));
+    }
+
+    #[test]
+    fn part1_example() {
+        for &(input, expected, _) in EXAMPLES {
+            assert_eq!(part1(&input_generator(input).unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn part2_example() {
+        for &(input, _, expected) in EXAMPLES {
+            assert_eq!(part2(&input_generator(input).unwrap()), expected);
+        }
+    }
+}
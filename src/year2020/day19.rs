@@ -0,0 +1,324 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use crate::util::{
+    integer, lines, literal, many, normalize, parse, preceded, satisfy, separated, take_while1,
+    ParseError, Parser,
+};
+
+#[derive(Debug, Clone)]
+pub enum Rule {
+    Single(char),
+    Union(Vec<Vec<usize>>),
+}
+
+/// Parses a rule body like `"a"` or `1 2 | 2 1`.
+fn rule<'a>() -> impl Parser<'a, Rule> {
+    let single = preceded(literal("\""), satisfy("a character", |c| c != '"'))
+        .skip(literal("\""))
+        .map(Rule::Single);
+    let sequence =
+        integer()
+            .then(many(preceded(literal(" "), integer())))
+            .map(|(first, mut rest)| {
+                rest.insert(0, first);
+                rest
+            });
+    single.or(separated(sequence, literal(" | ")).map(Rule::Union))
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, rule())
+    }
+}
+
+type Input = (HashMap<usize, Rule>, Vec<String>);
+
+#[aoc_generator(day19)]
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let input = &normalize(input);
+    let numbered_rule = integer().skip(literal(": ")).then(rule());
+    let message = take_while1("message", |c| !c.is_whitespace()).map(str::to_owned);
+    let parser = lines(numbered_rule)
+        .skip(literal("\n\n"))
+        .then(lines(message))
+        .map(|(rules, messages)| (rules.into_iter().collect(), messages));
+    parse(input, parser).map_err(|e| e.in_day(19))
+}
+
+fn match_rule_in_state<'a>(
+    rule: &Rule,
+    rules: &HashMap<usize, Rule>,
+    state: &'a str,
+) -> HashSet<&'a str> {
+    match rule {
+        Rule::Single(c) => state.strip_prefix(*c).into_iter().collect(),
+        Rule::Union(options) => {
+            // Try all of the options
+            options
+                .iter()
+                .flat_map(|sequence| {
+                    // Match all sub rules in sequence
+                    sequence
+                        .iter()
+                        .fold(Some(state).into_iter().collect(), |states, sub_rule| {
+                            match_rule(*sub_rule, rules, &states)
+                        })
+                })
+                .collect()
+        }
+    }
+}
+
+fn match_rule<'a>(
+    rule_id: usize,
+    rules: &HashMap<usize, Rule>,
+    states: &HashSet<&'a str>,
+) -> HashSet<&'a str> {
+    if states.is_empty() {
+        return HashSet::new();
+    }
+    let rule = rules.get(&rule_id).unwrap();
+    // Advance through all possible states simultaneously
+    states
+        .iter()
+        .flat_map(|&state| match_rule_in_state(rule, rules, state))
+        .collect()
+}
+
+fn match_rule_complete(rule_id: usize, rules: &HashMap<usize, Rule>, s: &str) -> bool {
+    let final_states = match_rule(rule_id, rules, &Some(s).into_iter().collect());
+    // At least one match must have consumed the entire string
+    final_states.contains(&"")
+}
+
+#[aoc(day19, part1)]
+pub fn part1(input: &Input) -> usize {
+    let (rules, messages) = input;
+    messages
+        .iter()
+        .filter(|message| match_rule_complete(0, rules, message))
+        .count()
+}
+
+#[aoc(day19, part2)]
+pub fn part2(input: &Input) -> usize {
+    let (rules, messages) = input;
+    let mut rules = rules.clone();
+    rules.insert(8, "42 | 42 8".parse().unwrap());
+    rules.insert(11, "42 31 | 42 11 31".parse().unwrap());
+
+    messages
+        .iter()
+        .filter(|message| match_rule_complete(0, &rules, message))
+        .count()
+}
+
+/// Returns a cycle of rules which refer to each other, like `[8, 8]`, if there is one.
+fn find_cycle(rules: &HashMap<usize, Rule>) -> Option<Vec<usize>> {
+    fn visit(
+        id: usize,
+        rules: &HashMap<usize, Rule>,
+        path: &mut Vec<usize>,
+        done: &mut HashSet<usize>,
+    ) -> Option<Vec<usize>> {
+        if let Some(start) = path.iter().position(|&other| other == id) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(id);
+            return Some(cycle);
+        }
+        if !done.insert(id) {
+            return None;
+        }
+        path.push(id);
+        if let Some(Rule::Union(options)) = rules.get(&id) {
+            for &sub_rule in options.iter().flatten() {
+                if let Some(cycle) = visit(sub_rule, rules, path, done) {
+                    return Some(cycle);
+                }
+            }
+        }
+        path.pop();
+        None
+    }
+
+    let mut ids = rules.keys().copied().collect::<Vec<_>>();
+    ids.sort_unstable();
+    let mut done = HashSet::new();
+    ids.into_iter()
+        .find_map(|id| visit(id, rules, &mut vec![], &mut done))
+}
+
+/// Checks that rule numbers are unique, that every rule which is referred to (including rules
+/// 0, 42 and 31, which are used by both parts) is defined, that no rules refer to each other
+/// in a cycle, and that messages only contain characters which rules can match.
+pub fn validate(input: &str) -> Result<Vec<String>, ParseError> {
+    let (rules, messages) = input_generator(input)?;
+    let mut violations = vec![];
+
+    // Duplicate rules are merged when parsing, so look at the rule numbers in the input itself
+    let input = normalize(input);
+    let mut seen = HashSet::new();
+    for line in input.lines().take_while(|line| !line.is_empty()) {
+        let id = line.split(':').next().unwrap();
+        if !seen.insert(id) {
+            violations.push(format!("rule {} is defined twice", id));
+        }
+    }
+
+    for id in [0, 42, 31] {
+        if !rules.contains_key(&id) {
+            violations.push(format!("rule {} is not defined", id));
+        }
+    }
+    let mut ids = rules.keys().copied().collect::<Vec<_>>();
+    ids.sort_unstable();
+    for id in &ids {
+        if let Rule::Union(options) = &rules[id] {
+            let mut undefined = options
+                .iter()
+                .flatten()
+                .filter(|sub_rule| !rules.contains_key(sub_rule))
+                .collect::<Vec<_>>();
+            undefined.sort_unstable();
+            undefined.dedup();
+            for sub_rule in undefined {
+                violations.push(format!(
+                    "rule {} refers to rule {}, which is not defined",
+                    id, sub_rule
+                ));
+            }
+        }
+    }
+    if let Some(cycle) = find_cycle(&rules) {
+        let cycle = cycle.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        violations.push(format!("rules refer to each other: {}", cycle.join(" -> ")));
+    }
+
+    let characters = rules
+        .values()
+        .filter_map(|rule| match rule {
+            Rule::Single(c) => Some(*c),
+            Rule::Union(_) => None,
+        })
+        .collect::<HashSet<_>>();
+    for (i, message) in messages.iter().enumerate() {
+        if let Some(c) = message.chars().find(|c| !characters.contains(c)) {
+            violations.push(format!(
+                "message {} contains `{}`, which no rule matches",
+                i + 1,
+                c
+            ));
+        }
+    }
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day19::{input_generator, part1, part2, validate, Rule};
+
+    const EXAMPLE: &str = "\
+0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: \"a\"
+5: \"b\"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb";
+
+    const EXAMPLE_2: &str = "\
+42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: \"a\"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: \"b\"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba";
+
+    #[test]
+    fn generator() {
+        let (rules, messages) = input_generator(EXAMPLE).unwrap();
+        assert_eq!(rules.len(), 6);
+        assert!(matches!(rules[&4], Rule::Single('a')));
+        assert!(
+            matches!(&rules[&1], Rule::Union(options) if options == &vec![vec![2, 3], vec![3, 2]])
+        );
+        assert_eq!(messages.len(), 5);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 2);
+        assert_eq!(part1(&input_generator(EXAMPLE_2).unwrap()), 3);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE_2).unwrap()), 12);
+    }
+
+    #[test]
+    fn validation() {
+        assert!(validate(EXAMPLE_2).unwrap().is_empty());
+        assert_eq!(
+            validate("0: 1 2 | 3\n1: \"a\"\n3: 0 1\n1: \"b\"\n\nbb\nbc").unwrap(),
+            vec![
+                "rule 1 is defined twice",
+                "rule 42 is not defined",
+                "rule 31 is not defined",
+                "rule 0 refers to rule 2, which is not defined",
+                "rules refer to each other: 0 -> 3 -> 0",
+                "message 2 contains `c`, which no rule matches",
+            ]
+        );
+    }
+}
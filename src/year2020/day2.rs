@@ -0,0 +1,444 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::util::{
+    integer, lines, literal, normalize, optional, parse, preceded, satisfy, separated, take_while1,
+    word, Input, ParseError, ParseResult, Parser,
+};
+
+/// The policy on a line of the input, like `1-3 a`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Policy {
+    letter: char,
+    left: usize,
+    right: usize,
+}
+
+impl Policy {
+    /// Returns true if the password contains the letter `left` to `right` times.
+    pub fn matches_part1(&self, password: &str) -> bool {
+        let count = password.chars().filter(|&c| c == self.letter).count();
+        self.left <= count && count <= self.right
+    }
+
+    /// Returns true if exactly one of the (1-based) positions `left` and `right` of the password
+    /// contains the letter. Positions count characters, and positions beyond the password
+    /// do not contain the letter.
+    pub fn matches_part2(&self, password: &str) -> bool {
+        if !self.letter.is_ascii() || !password.is_ascii() {
+            return self.matches_part2_chars(password);
+        }
+        // Every character is a single byte, so positions are byte indices
+        let letter = Some(self.letter as u8);
+        let at = |position: usize| {
+            position
+                .checked_sub(1)
+                .and_then(|index| password.as_bytes().get(index))
+                .copied()
+        };
+        (at(self.left) == letter) != (at(self.right) == letter)
+    }
+
+    /// Like [`Policy::matches_part2`], but without the fast path for ASCII passwords.
+    /// Walks the characters of the password once, without allocating.
+    pub fn matches_part2_chars(&self, password: &str) -> bool {
+        let (first, second) = (self.left.min(self.right), self.left.max(self.right));
+        let mut chars = password.chars();
+        // A first position of 0 skips nothing, so the second one is still found
+        let at_first = first.checked_sub(1).and_then(|index| chars.nth(index));
+        let at_second = if first == second {
+            at_first
+        } else {
+            chars.nth(second - first - 1)
+        };
+        (at_first == Some(self.letter)) != (at_second == Some(self.letter))
+    }
+
+    fn at_position(&self, password: &str, position: usize) -> bool {
+        position
+            .checked_sub(1)
+            .and_then(|index| password.chars().nth(index))
+            == Some(self.letter)
+    }
+}
+
+impl Display for Policy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{} {}", self.left, self.right, self.letter)
+    }
+}
+
+/// Parses a policy like `1-3 a`.
+fn policy<'a>() -> impl Parser<'a, Policy> {
+    integer()
+        .skip(literal("-"))
+        .then(integer())
+        .skip(literal(" "))
+        .then(satisfy("a letter", char::is_alphabetic))
+        .map(|((left, right), letter)| Policy {
+            letter,
+            left,
+            right,
+        })
+}
+
+impl FromStr for Policy {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, policy())
+    }
+}
+
+#[aoc_generator(day2)]
+pub fn input_generator(input: &str) -> Result<Vec<(Policy, String)>, ParseError> {
+    let input = &normalize(input);
+    let password = word().map(str::to_owned);
+    parse(input, lines(policy().skip(literal(": ")).then(password))).map_err(|e| e.in_day(2))
+}
+
+#[aoc(day2, part1)]
+pub fn part1(input: &[(Policy, String)]) -> usize {
+    input
+        .iter()
+        .filter(|(policy, password)| policy.matches_part1(password))
+        .count()
+}
+
+#[aoc(day2, part2)]
+pub fn part2(input: &[(Policy, String)]) -> usize {
+    input
+        .iter()
+        .filter(|(policy, password)| policy.matches_part2(password))
+        .count()
+}
+
+#[aoc(day2, part2, chars)]
+pub fn part2_chars(input: &[(Policy, String)]) -> usize {
+    input
+        .iter()
+        .filter(|(policy, password)| policy.matches_part2_chars(password))
+        .count()
+}
+
+/// A rule which passwords must follow.
+///
+/// Rules are displayed in the syntax of policy files (see [`parse_policy_file`]).
+pub trait PasswordRule: Display {
+    /// Checks a password, together with the policy on its line of the input.
+    /// Returns why the password breaks the rule if it does.
+    fn check(&self, policy: &Policy, password: &str) -> Result<(), String>;
+}
+
+/// The letter must occur a number of times in a range, as in part 1.
+/// Uses the policy of the password's line if no policy is given.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CountRange(pub Option<Policy>);
+
+impl PasswordRule for CountRange {
+    fn check(&self, policy: &Policy, password: &str) -> Result<(), String> {
+        let policy = self.0.as_ref().unwrap_or(policy);
+        if policy.matches_part1(password) {
+            return Ok(());
+        }
+        let count = password.chars().filter(|&c| c == policy.letter).count();
+        Err(format!(
+            "`{}`: contains `{}` {} times, expected {} to {}",
+            self, policy.letter, count, policy.left, policy.right
+        ))
+    }
+}
+
+impl Display for CountRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(policy) => write!(f, "count {}", policy),
+            None => write!(f, "count"),
+        }
+    }
+}
+
+/// The letter must be at exactly one of two positions, as in part 2.
+/// Uses the policy of the password's line if no policy is given.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Positions(pub Option<Policy>);
+
+impl PasswordRule for Positions {
+    fn check(&self, policy: &Policy, password: &str) -> Result<(), String> {
+        let policy = self.0.as_ref().unwrap_or(policy);
+        if policy.matches_part2(password) {
+            return Ok(());
+        }
+        let found = if policy.at_position(password, policy.left) {
+            "both"
+        } else {
+            "neither"
+        };
+        Err(format!(
+            "`{}`: `{}` is at {} of positions {} and {}",
+            self, policy.letter, found, policy.left, policy.right
+        ))
+    }
+}
+
+impl Display for Positions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(policy) => write!(f, "positions {}", policy),
+            None => write!(f, "positions"),
+        }
+    }
+}
+
+/// None of the characters may occur in the password.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Forbidden(pub String);
+
+impl PasswordRule for Forbidden {
+    fn check(&self, _: &Policy, password: &str) -> Result<(), String> {
+        match password.chars().find(|&c| self.0.contains(c)) {
+            Some(c) => Err(format!("`{}`: contains `{}`", self, c)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for Forbidden {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "forbid {}", self.0)
+    }
+}
+
+/// The password must have at least this many characters.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MinLength(pub usize);
+
+impl PasswordRule for MinLength {
+    fn check(&self, _: &Policy, password: &str) -> Result<(), String> {
+        let len = password.chars().count();
+        if len >= self.0 {
+            Ok(())
+        } else {
+            Err(format!("`{}`: has {} characters", self, len))
+        }
+    }
+}
+
+impl Display for MinLength {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "min-length {}", self.0)
+    }
+}
+
+/// All of the rules must hold. Reports the first rule which fails.
+pub struct All(pub Vec<Box<dyn PasswordRule>>);
+
+impl PasswordRule for All {
+    fn check(&self, policy: &Policy, password: &str) -> Result<(), String> {
+        self.0
+            .iter()
+            .try_for_each(|rule| rule.check(policy, password))
+    }
+}
+
+/// At least one of the rules must hold. Reports all rules if none holds.
+pub struct Any(pub Vec<Box<dyn PasswordRule>>);
+
+impl PasswordRule for Any {
+    fn check(&self, policy: &Policy, password: &str) -> Result<(), String> {
+        let mut failures = vec![];
+        for rule in &self.0 {
+            match rule.check(policy, password) {
+                Ok(()) => return Ok(()),
+                Err(failure) => failures.push(failure),
+            }
+        }
+        Err(format!("`{}`: {}", self, failures.join("; ")))
+    }
+}
+
+fn fmt_rules(
+    f: &mut Formatter<'_>,
+    name: &str,
+    rules: &[Box<dyn PasswordRule>],
+) -> std::fmt::Result {
+    write!(f, "{}(", name)?;
+    for (i, rule) in rules.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", rule)?;
+    }
+    write!(f, ")")
+}
+
+impl Display for All {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_rules(f, "all", &self.0)
+    }
+}
+
+impl Display for Any {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_rules(f, "any", &self.0)
+    }
+}
+
+/// Parses a rule like `count 1-3 a`, `positions`, `forbid xyz`, `min-length 8`,
+/// or a combination like `any(count, all(positions, min-length 8))`.
+fn rule(input: Input<'_>) -> ParseResult<'_, Box<dyn PasswordRule>> {
+    let given_policy = optional(preceded(literal(" "), policy()));
+    let rules = || preceded(literal("("), separated(rule, literal(", "))).skip(literal(")"));
+    let (name, rest) = take_while1("rule", |c| c.is_ascii_lowercase() || c == '-').parse(input)?;
+    match name {
+        "count" => given_policy
+            .map(|policy| Box::new(CountRange(policy)) as Box<dyn PasswordRule>)
+            .parse(rest),
+        "positions" => given_policy
+            .map(|policy| Box::new(Positions(policy)) as Box<dyn PasswordRule>)
+            .parse(rest),
+        "forbid" => {
+            let characters =
+                take_while1("characters", |c| !c.is_whitespace() && !",()".contains(c));
+            preceded(literal(" "), characters)
+                .map(|characters| {
+                    Box::new(Forbidden(characters.to_owned())) as Box<dyn PasswordRule>
+                })
+                .parse(rest)
+        }
+        "min-length" => preceded(literal(" "), integer())
+            .map(|len| Box::new(MinLength(len)) as Box<dyn PasswordRule>)
+            .parse(rest),
+        "all" => rules()
+            .map(|rules| Box::new(All(rules)) as Box<dyn PasswordRule>)
+            .parse(rest),
+        "any" => rules()
+            .map(|rules| Box::new(Any(rules)) as Box<dyn PasswordRule>)
+            .parse(rest),
+        _ => Err(input.error_at(
+            name,
+            "`count`, `positions`, `forbid`, `min-length`, `all` or `any`",
+        )),
+    }
+}
+
+/// Parses a policy file: one rule per line, which all must hold.
+/// Blank lines and lines starting with `#` are ignored.
+///
+/// ```text
+/// # The policy of each line, and no vowels
+/// count
+/// forbid aeiou
+/// any(min-length 8, positions 1-2 x)
+/// ```
+pub fn parse_policy_file(input: &str) -> Result<All, ParseError> {
+    let input = &normalize(input);
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| parse(line, rule).map_err(|e| e.offset_lines(i)))
+        .collect::<Result<_, _>>()
+        .map(All)
+}
+
+/// Checks every password of the input against a rule, and returns the failures
+/// for each (0-based) line.
+pub fn check_passwords(
+    rule: &dyn PasswordRule,
+    input: &[(Policy, String)],
+) -> Vec<Result<(), String>> {
+    input
+        .iter()
+        .map(|(policy, password)| rule.check(policy, password))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day2::*;
+
+    const EXAMPLE: &str = "\
+1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+    #[test]
+    fn generator() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input.len(), 3);
+        let (policy, password) = &input[1];
+        assert_eq!((policy.left, policy.right, policy.letter), (1, 3, 'b'));
+        assert_eq!(password, "cdefg");
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 1);
+    }
+
+    #[test]
+    fn positions_beyond_password() {
+        let policy: Policy = "2-9 c".parse().unwrap();
+        assert!(policy.matches_part2("ac"));
+        assert!(!policy.matches_part2("ab"));
+        let policy: Policy = "0-1 c".parse().unwrap();
+        assert!(policy.matches_part2("c"));
+    }
+
+    #[test]
+    fn unicode_passwords() {
+        let input = input_generator("1-2 é: éaé\n2-3 a: ééa\n1-2 a: aé").unwrap();
+        assert_eq!(part1(&input), 2);
+        assert_eq!((part2(&input), part2_chars(&input)), (3, 3));
+        let policy: Policy = "3-1 a".parse().unwrap();
+        assert!(policy.matches_part2("ééa"));
+        assert!(policy.matches_part2_chars("ééa"));
+    }
+
+    #[test]
+    fn ascii_matches_chars() {
+        let input = crate::year2020::gen::random_input(2, 7, 1000).unwrap();
+        let input = input_generator(&input).unwrap();
+        assert_eq!(part2(&input), part2_chars(&input));
+    }
+
+    #[test]
+    fn policy_file() {
+        let rules = parse_policy_file(
+            "# no c or vowels but a\ncount\n\nany(forbid ceiou, all(min-length 9, positions 1-2 c))\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules.to_string(),
+            "all(count, any(forbid ceiou, all(min-length 9, positions 1-2 c)))"
+        );
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(
+            check_passwords(&rules, &input),
+            vec![
+                Err("`any(forbid ceiou, all(min-length 9, positions 1-2 c))`: \
+                     `forbid ceiou`: contains `c`; `min-length 9`: has 5 characters"
+                    .to_owned()),
+                Err("`count`: contains `b` 0 times, expected 1 to 3".to_owned()),
+                Err("`any(forbid ceiou, all(min-length 9, positions 1-2 c))`: \
+                     `forbid ceiou`: contains `c`; `positions 1-2 c`: `c` is at both of positions 1 and 2"
+                    .to_owned()),
+            ]
+        );
+
+        let error = parse_policy_file("count\nany(count, length 3)")
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 12: expected `count`, `positions`, `forbid`, `min-length`, `all` \
+             or `any`, found `length`"
+        );
+    }
+}
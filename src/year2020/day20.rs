@@ -0,0 +1,526 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::util::{
+    backtrack, normalize, parse_blocks, parse_token, split_pair, strip_prefix, strip_suffix,
+    Backtrack, Frames, Grid, Palette, ParseError, Vector2D,
+};
+
+const TILE_SIZE: usize = 10;
+
+const SEA_MONSTER: &str = "
+                  #
+#    ##    ##    ###
+ #  #  #  #  #  #   ";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Tile {
+    grid: Grid<bool>,
+}
+
+fn parse_pixels(s: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::parse(s, "`#` or `.`", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+impl FromStr for Tile {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expected_row = format!("row of {} `#` or `.`", TILE_SIZE);
+        let grid = parse_pixels(s)?;
+        if grid.width() != TILE_SIZE {
+            return Err(ParseError::at(
+                s,
+                s.lines().next().unwrap_or(s),
+                expected_row,
+            ));
+        }
+        if grid.height() < TILE_SIZE {
+            return Err(ParseError::at(s, &s[s.len()..], expected_row));
+        }
+        if let Some(line) = s.lines().nth(TILE_SIZE) {
+            return Err(ParseError::at(s, line, "end of tile"));
+        }
+        Ok(Tile { grid })
+    }
+}
+
+fn pixels_to_string(grid: &Grid<bool>) -> String {
+    grid.map(|&cell| if cell { '#' } else { '.' }).to_string()
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", pixels_to_string(&self.grid))
+    }
+}
+
+type Input = Vec<(u32, Tile)>;
+
+#[aoc_generator(day20)]
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let input = &normalize(input);
+    parse_blocks(input, |s| {
+        let (id_line, tile_lines) = split_pair(s, s, "\n")?;
+        let id = strip_suffix(s, strip_prefix(s, id_line, "Tile ")?, ":")?;
+        let id = parse_token(s, id, "tile ID")?;
+        let tile = tile_lines
+            .parse()
+            .map_err(|e: ParseError| e.offset_lines(1))?;
+        Ok((id, tile))
+    })
+    .map_err(|e| e.in_day(20))
+}
+
+impl Tile {
+    fn border_top(&self) -> &[bool] {
+        self.grid.row(0)
+    }
+
+    fn border_bottom(&self) -> &[bool] {
+        self.grid.row(TILE_SIZE - 1)
+    }
+
+    fn border_left(&self) -> Vec<bool> {
+        self.grid.column(0).copied().collect()
+    }
+
+    fn border_right(&self) -> Vec<bool> {
+        self.grid.column(TILE_SIZE - 1).copied().collect()
+    }
+
+    /// Returns the four borders, each read in the direction in which it is smaller,
+    /// so that borders which can be matched (possibly after flipping a tile) are equal.
+    fn canonical_borders(&self) -> [Vec<bool>; 4] {
+        [
+            self.border_top().to_vec(),
+            self.border_bottom().to_vec(),
+            self.border_left(),
+            self.border_right(),
+        ]
+        .map(|border| {
+            let reversed = border.iter().rev().copied().collect::<Vec<_>>();
+            border.min(reversed)
+        })
+    }
+
+    fn permutations(&self) -> impl Iterator<Item = Tile> + '_ {
+        self.grid.transforms().map(|grid| Tile { grid })
+    }
+}
+
+/// A partial picture, with tiles placed row by row from the top left.
+struct Picture {
+    size: usize,
+    /// The IDs and all permutations of every tile.
+    tiles: Vec<(u32, Vec<Tile>)>,
+    /// The index and permutation of every placed tile.
+    placed: Vec<(usize, usize)>,
+    used: Vec<bool>,
+}
+
+impl Picture {
+    fn tile(&self, (index, permutation): (usize, usize)) -> &Tile {
+        &self.tiles[index].1[permutation]
+    }
+}
+
+impl Backtrack for Picture {
+    type Choice = (usize, usize);
+
+    fn is_complete(&self) -> bool {
+        self.placed.len() == self.tiles.len()
+    }
+
+    fn choices(&self) -> Vec<(usize, usize)> {
+        let pos = self.placed.len();
+        let expected_top = pos
+            .checked_sub(self.size)
+            .map(|above| self.tile(self.placed[above]).border_bottom());
+        let expected_left = if pos.is_multiple_of(self.size) {
+            None
+        } else {
+            Some(self.tile(self.placed[pos - 1]).border_right())
+        };
+        let mut choices = vec![];
+        for (index, (_, permutations)) in self.tiles.iter().enumerate() {
+            if self.used[index] {
+                continue;
+            }
+            for (permutation, tile) in permutations.iter().enumerate() {
+                if expected_top.is_some_and(|expected| tile.border_top() != expected) {
+                    continue;
+                }
+                if expected_left
+                    .as_ref()
+                    .is_some_and(|expected| &tile.border_left() != expected)
+                {
+                    continue;
+                }
+                choices.push((index, permutation));
+            }
+        }
+        choices
+    }
+
+    fn apply(&mut self, &(index, permutation): &(usize, usize)) {
+        self.placed.push((index, permutation));
+        self.used[index] = true;
+    }
+
+    fn undo(&mut self, &(index, _): &(usize, usize)) {
+        self.placed.pop();
+        self.used[index] = false;
+    }
+}
+
+fn place_tiles(size: i32, input: &Input) -> Option<HashMap<Vector2D, (u32, Tile)>> {
+    let mut picture = Picture {
+        size: size as usize,
+        tiles: input
+            .iter()
+            .map(|(id, tile)| (*id, tile.permutations().collect()))
+            .collect(),
+        placed: vec![],
+        used: vec![false; input.len()],
+    };
+    if !backtrack(&mut picture) {
+        return None;
+    }
+    let solution = picture
+        .placed
+        .iter()
+        .enumerate()
+        .map(|(i, &choice)| {
+            let pos = Vector2D::new((i % picture.size) as i32, (i / picture.size) as i32);
+            (
+                pos,
+                (picture.tiles[choice.0].0, picture.tile(choice).clone()),
+            )
+        })
+        .collect();
+    Some(solution)
+}
+
+#[aoc(day20, part1)]
+pub fn part1(input: &Input) -> u64 {
+    let size = (input.len() as f32).sqrt() as i32;
+    let solution = place_tiles(size, input).unwrap();
+
+    [
+        solution.get(&Vector2D::new(0, 0)),
+        solution.get(&Vector2D::new(size - 1, 0)),
+        solution.get(&Vector2D::new(0, size - 1)),
+        solution.get(&Vector2D::new(size - 1, size - 1)),
+    ]
+    .iter()
+    .map(|x| x.unwrap().0 as u64)
+    .product()
+}
+
+type Image = Grid<bool>;
+
+fn create_image(size: usize, tiles: &HashMap<Vector2D, (u32, Tile)>) -> Image {
+    // Remove the border of each tile
+    const INNER_SIZE: usize = TILE_SIZE - 2;
+    let inner_tiles = tiles
+        .iter()
+        .map(|(&pos, (_, tile))| {
+            let inner = tile
+                .grid
+                .sub_grid(Vector2D::new(1, 1), INNER_SIZE, INNER_SIZE);
+            (pos, inner)
+        })
+        .collect::<HashMap<_, _>>();
+    Grid::from_fn(size * INNER_SIZE, size * INNER_SIZE, |pos| {
+        let inner_size = INNER_SIZE as i32;
+        let tile_pos = pos / inner_size;
+        let pos_in_tile = Vector2D::new(pos.x() % inner_size, pos.y() % inner_size);
+        inner_tiles[&tile_pos][pos_in_tile]
+    })
+}
+
+fn create_sea_monster_pattern() -> Vec<Vector2D> {
+    SEA_MONSTER
+        .lines()
+        .skip(1)
+        .enumerate()
+        .flat_map(move |(y, line)| {
+            line.chars().enumerate().filter_map(move |(x, char)| {
+                if char == '#' {
+                    Some(Vector2D::new(x as i32, y as i32))
+                } else {
+                    None
+                }
+            })
+        })
+        .collect()
+}
+
+fn find_image_pattern(image: &Image, pattern: &[Vector2D]) -> Vec<Vector2D> {
+    image
+        .positions()
+        .filter(|&start| {
+            pattern
+                .iter()
+                .all(|&pos| image.get(start + pos) == Some(&true))
+        })
+        .collect()
+}
+
+/// Assembles the image, and returns it in the orientation in which it contains sea monsters,
+/// together with the top left corners of the sea monsters.
+fn find_sea_monsters(input: &Input) -> Option<(Image, Vec<Vector2D>)> {
+    let size = (input.len() as f32).sqrt() as i32;
+    let tiles = place_tiles(size, input)?;
+    let image = create_image(size as usize, &tiles);
+    let pattern = create_sea_monster_pattern();
+    let found = image.transforms().find_map(|perm| {
+        let monsters = find_image_pattern(&perm, &pattern);
+        if monsters.is_empty() {
+            None
+        } else {
+            Some((perm, monsters))
+        }
+    });
+    found
+}
+
+#[aoc(day20, part2)]
+pub fn part2(input: &Input) -> usize {
+    let (image, monsters) = find_sea_monsters(input).expect("no monsters found");
+    let count_things = image.values().filter(|&&cell| cell).count();
+    count_things - (monsters.len() * create_sea_monster_pattern().len())
+}
+
+/// Renders the assembled image with the sea monsters, as `image-0000`.
+/// Water, rough water and sea monsters are colors 0, 1 and 2 of the palette.
+pub fn render(input: &str, frames: &mut Frames) -> Result<(), Box<dyn Error>> {
+    let input = input_generator(input)?;
+    let (image, monsters) = find_sea_monsters(&input).ok_or("no monsters found")?;
+    let mut frame = Grid::from_fn(image.width(), image.height(), |pos| u8::from(image[pos]));
+    for start in monsters {
+        for pos in create_sea_monster_pattern() {
+            frame[start + pos] = 2;
+        }
+    }
+    let palette = Palette(vec![[16, 48, 96], [64, 112, 176], [64, 224, 96]]);
+    frames.write("image", &frame, &palette)?;
+    Ok(())
+}
+
+/// Checks that the tiles form a square with unique tile IDs, that no border matches more than one
+/// other tile, and that there are four corner tiles (with two borders that match no other tile).
+pub fn validate(input: &str) -> Result<Vec<String>, ParseError> {
+    let tiles = input_generator(input)?;
+    let mut violations = vec![];
+    let size = (tiles.len() as f64).sqrt().round() as usize;
+    if tiles.is_empty() || size * size != tiles.len() {
+        violations.push(format!("{} tiles cannot form a square image", tiles.len()));
+    }
+    let mut ids = HashMap::new();
+    for (i, &(id, _)) in tiles.iter().enumerate() {
+        if let Some(first) = ids.insert(id, i + 1) {
+            violations.push(format!(
+                "tile {} is number {} and {} in the input",
+                id,
+                first,
+                i + 1
+            ));
+        }
+    }
+
+    let mut borders = HashMap::<Vec<bool>, Vec<u32>>::new();
+    for (id, tile) in &tiles {
+        for border in tile.canonical_borders() {
+            borders.entry(border).or_default().push(*id);
+        }
+    }
+    let mut shared = borders
+        .values()
+        .filter(|ids| ids.len() > 2)
+        .map(|ids| {
+            let ids = ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+            format!("tiles {} share a border", ids.join(", "))
+        })
+        .collect::<Vec<_>>();
+    shared.sort_unstable();
+    violations.extend(shared);
+
+    if size > 1 {
+        let corners = tiles
+            .iter()
+            .filter(|(_, tile)| {
+                let tile_borders = tile.canonical_borders();
+                let unmatched = tile_borders
+                    .iter()
+                    .filter(|border| borders[*border].len() == 1)
+                    .count();
+                unmatched == 2
+            })
+            .count();
+        if corners != 4 {
+            violations.push(format!(
+                "expected 4 corner tiles with two unmatched borders, found {}",
+                corners
+            ));
+        }
+    }
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day20::{input_generator, part1, part2, validate};
+
+    const EXAMPLE: &str = "\
+Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...";
+
+    #[test]
+    fn generator() {
+        let tiles = input_generator(EXAMPLE).unwrap();
+        assert_eq!(tiles.len(), 9);
+        let (id, tile) = &tiles[0];
+        assert_eq!(*id, 2311);
+        assert_eq!(tile.to_string().lines().next(), Some("..##.#..#."));
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 20899048083289);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 273);
+    }
+
+    #[test]
+    fn validation() {
+        assert!(validate(EXAMPLE).unwrap().is_empty());
+        let first_tile = &EXAMPLE[..EXAMPLE.find("\n\n").unwrap()];
+        let input = format!("{}\n\n{}", EXAMPLE, first_tile);
+        assert_eq!(
+            validate(&input).unwrap(),
+            vec![
+                "10 tiles cannot form a square image",
+                "tile 2311 is number 1 and 10 in the input",
+                "tiles 2311, 1427, 2311 share a border",
+                "tiles 2311, 1951, 2311 share a border",
+                "tiles 2311, 3079, 2311 share a border",
+            ]
+        );
+    }
+}
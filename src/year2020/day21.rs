@@ -0,0 +1,206 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::util::{
+    backtrack, identifier, lines, literal, many, normalize, parse, preceded, separated, Backtrack,
+    ParseError, Parser,
+};
+
+#[derive(Debug)]
+pub struct Food {
+    ingredients: HashSet<String>,
+    allergens: HashSet<String>,
+}
+
+/// Parses a food like `mxmxvkd kfcds sqjhc nhms (contains dairy, fish)`.
+fn food<'a>() -> impl Parser<'a, Food> {
+    let ingredients = identifier().then(many(preceded(literal(" "), identifier())));
+    let allergens = separated(identifier(), literal(", "));
+    ingredients
+        .skip(literal(" (contains "))
+        .then(allergens)
+        .skip(literal(")"))
+        .map(|((first, rest), allergens)| Food {
+            ingredients: std::iter::once(first)
+                .chain(rest)
+                .map(str::to_owned)
+                .collect(),
+            allergens: allergens.into_iter().map(str::to_owned).collect(),
+        })
+}
+
+impl FromStr for Food {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, food())
+    }
+}
+
+#[aoc_generator(day21)]
+pub fn input_generator(input: &str) -> Result<Vec<Food>, ParseError> {
+    let input = &normalize(input);
+    parse(input, lines(food())).map_err(|e| e.in_day(21))
+}
+
+fn is_impossible_mapping(foods: &[Food], ingredient: &str, allergen: &str) -> bool {
+    // Find a counterexample, i.e. any food where this (ingredient, allergen) mapping
+    // would be invalid. That is: if it contains the allergen, but not the ingredient.
+    foods
+        .iter()
+        .any(|food| food.allergens.contains(allergen) && !food.ingredients.contains(ingredient))
+}
+
+fn get_ingredients_without_allergens(
+    foods: &[Food],
+    all_ingredients: &HashSet<String>,
+    all_allergens: &HashSet<String>,
+) -> HashSet<String> {
+    all_ingredients
+        .iter()
+        .filter(|&ingredient| {
+            // Check that there is a counterexample for all possible allergens when mapped to this ingredient.
+            all_allergens
+                .iter()
+                .all(|allergen| is_impossible_mapping(foods, ingredient, allergen))
+        })
+        .cloned()
+        .collect::<HashSet<_>>()
+}
+
+#[aoc(day21, part1)]
+pub fn part1(foods: &[Food]) -> usize {
+    let all_ingredients = foods
+        .iter()
+        .flat_map(|food| food.ingredients.iter().cloned())
+        .collect::<HashSet<_>>();
+    let all_allergens = foods
+        .iter()
+        .flat_map(|food| food.allergens.iter().cloned())
+        .collect::<HashSet<_>>();
+
+    let ingredients_without_allergens =
+        get_ingredients_without_allergens(foods, &all_ingredients, &all_allergens);
+
+    foods
+        .iter()
+        .map(|food| {
+            food.ingredients
+                .intersection(&ingredients_without_allergens)
+                .count()
+        })
+        .sum()
+}
+
+/// A partial mapping of the unknown ingredients to allergens, in the order of the ingredients.
+struct Mapping<'a> {
+    foods: &'a [Food],
+    ingredients: Vec<&'a str>,
+    allergens: Vec<&'a str>,
+    /// The index of the allergen of every mapped ingredient.
+    mapped: Vec<usize>,
+    used: Vec<bool>,
+}
+
+impl Backtrack for Mapping<'_> {
+    type Choice = usize;
+
+    fn is_complete(&self) -> bool {
+        self.mapped.len() == self.ingredients.len()
+    }
+
+    fn choices(&self) -> Vec<usize> {
+        let ingredient = self.ingredients[self.mapped.len()];
+        (0..self.allergens.len())
+            .filter(|&allergen| {
+                !self.used[allergen]
+                    && !is_impossible_mapping(self.foods, ingredient, self.allergens[allergen])
+            })
+            .collect()
+    }
+
+    fn apply(&mut self, &allergen: &usize) {
+        self.mapped.push(allergen);
+        self.used[allergen] = true;
+    }
+
+    fn undo(&mut self, &allergen: &usize) {
+        self.mapped.pop();
+        self.used[allergen] = false;
+    }
+}
+
+#[aoc(day21, part2)]
+pub fn part2(foods: &[Food]) -> String {
+    let all_ingredients = foods
+        .iter()
+        .flat_map(|food| food.ingredients.iter().cloned())
+        .collect::<HashSet<_>>();
+    let all_allergens = foods
+        .iter()
+        .flat_map(|food| food.allergens.iter().cloned())
+        .collect::<HashSet<_>>();
+
+    let ingredients_without_allergens =
+        get_ingredients_without_allergens(foods, &all_ingredients, &all_allergens);
+
+    let mut mapping = Mapping {
+        foods,
+        ingredients: all_ingredients
+            .difference(&ingredients_without_allergens)
+            .map(String::as_str)
+            .collect(),
+        allergens: all_allergens.iter().map(String::as_str).collect(),
+        mapped: vec![],
+        used: vec![false; all_allergens.len()],
+    };
+    assert_eq!(mapping.ingredients.len(), mapping.allergens.len());
+    assert!(backtrack(&mut mapping), "no mapping found");
+
+    let mut solution = mapping
+        .ingredients
+        .iter()
+        .zip(&mapping.mapped)
+        .map(|(&ingredient, &allergen)| (ingredient, mapping.allergens[allergen]))
+        .collect::<Vec<_>>();
+    solution.sort_unstable_by_key(|&(_, allergen)| allergen);
+    solution
+        .into_iter()
+        .map(|(ingredient, _)| ingredient)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day21::{input_generator, part1, part2};
+
+    const EXAMPLE: &str = "\
+mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+
+    #[test]
+    fn generator() {
+        let foods = input_generator(EXAMPLE).unwrap();
+        assert_eq!(foods.len(), 4);
+        assert_eq!(foods[0].ingredients.len(), 4);
+        assert!(foods[0].ingredients.contains("kfcds"));
+        assert_eq!(foods[0].allergens.len(), 2);
+        assert!(foods[0].allergens.contains("fish"));
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 5);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            part2(&input_generator(EXAMPLE).unwrap()),
+            "mxmxvkd,sqjhc,fvjkl"
+        );
+    }
+}
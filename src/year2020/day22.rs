@@ -0,0 +1,260 @@
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::util::{
+    integer, lines, literal, normalize, parse, section, NoTrace, ParseError, Parser, Trace,
+    TraceEvent,
+};
+use crate::Answer;
+
+type Input = (VecDeque<usize>, VecDeque<usize>);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Player {
+    Player1,
+    Player2,
+}
+
+impl Display for Player {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Player::Player1 => write!(f, "1"),
+            Player::Player2 => write!(f, "2"),
+        }
+    }
+}
+
+/// Returns the `play` event for a round, where `depth` is 0 for the outermost game.
+fn play_event(depth: usize, round: usize, top1: usize, top2: usize, winner: Player) -> TraceEvent {
+    TraceEvent::new("play")
+        .with("depth", depth)
+        .with("round", round)
+        .with("card1", top1)
+        .with("card2", top2)
+        .with("winner", winner)
+}
+
+/// Returns the `end` event for a game, with the winning deck.
+fn end_event(depth: usize, winner: Player, deck: &[usize]) -> TraceEvent {
+    let deck = deck.iter().map(|card| card.to_string()).collect::<Vec<_>>();
+    TraceEvent::new("end")
+        .with("depth", depth)
+        .with("winner", winner)
+        .with("deck", deck.join(","))
+}
+
+#[aoc_generator(day22)]
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let input = &normalize(input);
+    let deck = |header| section(header, lines(integer())).map(VecDeque::from);
+    let parser = deck("Player 1:")
+        .skip(literal("\n\n"))
+        .then(deck("Player 2:"));
+    parse(input, parser).map_err(|e| e.in_day(22))
+}
+
+fn play_round(deck1: &mut VecDeque<usize>, deck2: &mut VecDeque<usize>) -> (usize, usize, Player) {
+    let top1 = deck1.pop_front().unwrap();
+    let top2 = deck2.pop_front().unwrap();
+    if top1 > top2 {
+        deck1.push_back(top1);
+        deck1.push_back(top2);
+        (top1, top2, Player::Player1)
+    } else {
+        deck2.push_back(top2);
+        deck2.push_back(top1);
+        (top1, top2, Player::Player2)
+    }
+}
+
+fn play_game(
+    mut deck1: VecDeque<usize>,
+    mut deck2: VecDeque<usize>,
+    trace: &mut impl Trace,
+) -> (Player, Vec<usize>) {
+    let mut round = 0;
+    while !deck1.is_empty() && !deck2.is_empty() {
+        round += 1;
+        let (top1, top2, winner) = play_round(&mut deck1, &mut deck2);
+        trace.record(|| play_event(0, round, top1, top2, winner));
+    }
+    let (winner, mut winner_deck) = if deck1.is_empty() {
+        (Player::Player2, deck2)
+    } else {
+        (Player::Player1, deck1)
+    };
+    let winner_deck = winner_deck.make_contiguous().to_vec();
+    trace.record(|| end_event(0, winner, &winner_deck));
+    (winner, winner_deck)
+}
+
+fn player_score(deck: &[usize]) -> usize {
+    deck.iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &card)| (i + 1) * card)
+        .sum()
+}
+
+#[aoc(day22, part1)]
+pub fn part1(input: &Input) -> usize {
+    let (deck1, deck2) = input.clone();
+    let (_, winner_deck) = play_game(deck1, deck2, &mut NoTrace);
+    player_score(&winner_deck)
+}
+
+fn play_recursive_round(
+    deck1: &mut VecDeque<usize>,
+    deck2: &mut VecDeque<usize>,
+    depth: usize,
+    round: usize,
+    trace: &mut impl Trace,
+) {
+    let top1 = deck1.pop_front().unwrap();
+    let top2 = deck2.pop_front().unwrap();
+    let winner = if deck1.len() >= top1 && deck2.len() >= top2 {
+        // Winner is determined by a sub-game
+        let mut deck1 = deck1.clone();
+        let mut deck2 = deck2.clone();
+        deck1.truncate(top1);
+        deck2.truncate(top2);
+        let (winner, _) = play_recursive_game(deck1, deck2, depth + 1, trace);
+        winner
+    } else {
+        // Winner is the higher-value card
+        if top1 > top2 {
+            Player::Player1
+        } else {
+            Player::Player2
+        }
+    };
+    trace.record(|| play_event(depth, round, top1, top2, winner));
+    if winner == Player::Player1 {
+        deck1.push_back(top1);
+        deck1.push_back(top2);
+    } else {
+        deck2.push_back(top2);
+        deck2.push_back(top1);
+    }
+}
+
+fn play_recursive_game(
+    mut deck1: VecDeque<usize>,
+    mut deck2: VecDeque<usize>,
+    depth: usize,
+    trace: &mut impl Trace,
+) -> (Player, Vec<usize>) {
+    let mut previous_decks = HashSet::<(VecDeque<usize>, VecDeque<usize>)>::new();
+    let mut round = 0;
+    while !deck1.is_empty() && !deck2.is_empty() {
+        round += 1;
+        let current_decks = (deck1.clone(), deck2.clone());
+        if previous_decks.contains(&current_decks) {
+            trace.record(|| {
+                TraceEvent::new("repeat")
+                    .with("depth", depth)
+                    .with("round", round)
+            });
+            break; // Recursion!
+        } else {
+            previous_decks.insert(current_decks);
+        }
+        play_recursive_round(&mut deck1, &mut deck2, depth, round, trace);
+    }
+    // Player 1 wins if player 2's deck is empty, or because of recursion.
+    // Player 2 wins if player 1's deck is empty.
+    let (winner, mut winner_deck) = if deck1.is_empty() {
+        (Player::Player2, deck2)
+    } else {
+        (Player::Player1, deck1)
+    };
+    let winner_deck = winner_deck.make_contiguous().to_vec();
+    trace.record(|| end_event(depth, winner, &winner_deck));
+    (winner, winner_deck)
+}
+
+#[aoc(day22, part2)]
+pub fn part2(input: &Input) -> usize {
+    let (deck1, deck2) = input.clone();
+    let (_, winner_deck) = play_recursive_game(deck1, deck2, 0, &mut NoTrace);
+    player_score(&winner_deck)
+}
+
+/// Runs a part with tracing: every round is a `play` event with the cards and the winner,
+/// and every game ends with an `end` event. In part 2, `depth` is the depth of the sub-game,
+/// and a game which ends because of a repeated round has a `repeat` event.
+pub fn trace(input: &str, part: u8, trace: &mut impl Trace) -> Result<Answer, Box<dyn Error>> {
+    let (deck1, deck2) = input_generator(input)?;
+    let (_, winner_deck) = match part {
+        1 => play_game(deck1, deck2, trace),
+        2 => play_recursive_game(deck1, deck2, 0, trace),
+        part => return Err(format!("no part {}", part).into()),
+    };
+    Ok(player_score(&winner_deck).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::TraceEvent;
+    use crate::year2020::day22::{input_generator, part1, part2, trace};
+    use crate::Answer;
+
+    const EXAMPLE: &str = "\
+Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10";
+
+    const EXAMPLE_LOOP: &str = "\
+Player 1:
+43
+19
+
+Player 2:
+2
+29
+14";
+
+    #[test]
+    fn generator() {
+        let (deck1, deck2) = input_generator(EXAMPLE).unwrap();
+        assert_eq!(deck1, vec![9, 2, 6, 3, 1]);
+        assert_eq!(deck2, vec![5, 8, 4, 7, 10]);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 306);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 291);
+        // This game would loop forever without the infinite game prevention rule
+        part2(&input_generator(EXAMPLE_LOOP).unwrap());
+    }
+
+    #[test]
+    fn traced() {
+        let mut events = Vec::<TraceEvent>::new();
+        assert_eq!(trace(EXAMPLE, 2, &mut events).unwrap(), Answer::from(291));
+        assert_eq!(
+            events[0].to_string(),
+            "0 play depth=0 round=1 card1=9 card2=5 winner=1"
+        );
+        let end = events.last().unwrap();
+        assert_eq!(end.kind, "end");
+        assert_eq!(end.get("deck"), Some("7,5,6,2,4,1,10,8,9,3"));
+        assert!(events.iter().any(|event| event.get("depth") == Some("1")));
+    }
+}
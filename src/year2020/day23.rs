@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::iter::FromIterator;
+
+use crate::util::{normalize, NoTrace, ParseError, Trace, TraceEvent};
+use crate::Answer;
+
+#[derive(Debug)]
+struct Cups {
+    head: u32,
+    next: HashMap<u32, u32>,
+}
+
+impl FromIterator<u32> for Cups {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut it = iter.into_iter();
+
+        // Head
+        let head = it.next().unwrap();
+        let mut next = HashMap::new();
+
+        let mut prev = head;
+        for current in it {
+            next.insert(prev, current);
+            prev = current;
+        }
+
+        // Connect tail to head
+        next.insert(prev, head);
+
+        Cups { head, next }
+    }
+}
+
+impl Cups {
+    fn len(&self) -> usize {
+        self.next.len()
+    }
+
+    fn head(&self) -> u32 {
+        self.head
+    }
+
+    fn set_head(&mut self, label: u32) {
+        self.head = label;
+    }
+
+    fn advance_head(&mut self) {
+        self.head = self.get_next(self.head);
+    }
+
+    fn get_next(&self, label: u32) -> u32 {
+        *self.next.get(&label).unwrap()
+    }
+
+    fn remove(&mut self, prev_label: u32, label: u32) {
+        assert_eq!(self.get_next(prev_label), label);
+        let next_label = self.get_next(label);
+        self.next.insert(prev_label, next_label);
+        if label == self.head {
+            self.head = next_label;
+        }
+    }
+
+    fn insert_after(&mut self, ref_label: u32, new_label: u32) {
+        let next_label = self.get_next(ref_label);
+        self.next.insert(ref_label, new_label);
+        self.next.insert(new_label, next_label);
+    }
+
+    fn into_vec(self) -> Vec<u32> {
+        let mut result = vec![];
+        let mut label = self.head;
+        loop {
+            result.push(label);
+            label = self.get_next(label);
+            if label == self.head {
+                break;
+            }
+        }
+        result
+    }
+}
+
+/// Parses the labels of the cups, which must be at least 5 cups labeled 1 to n,
+/// with every label used once.
+#[aoc_generator(day23)]
+pub fn input_generator(input: &str) -> Result<Vec<u32>, ParseError> {
+    let input = &normalize(input);
+    let cups = parse_labels(input).map_err(|e| e.in_day(23))?;
+    match label_errors(input, &cups).into_iter().next() {
+        Some(error) => Err(error.in_day(23)),
+        None => Ok(cups),
+    }
+}
+
+fn parse_labels(input: &str) -> Result<Vec<u32>, ParseError> {
+    input
+        .char_indices()
+        .map(|(i, char)| {
+            char.to_digit(10)
+                .ok_or_else(|| ParseError::at_char(input, i, "cup label"))
+        })
+        .collect()
+}
+
+/// Checks that the labels are a permutation of 1 to n, with n at least 5,
+/// since the game needs a destination cup besides the current cup and the picked up cups.
+fn label_errors(input: &str, cups: &[u32]) -> Vec<ParseError> {
+    let mut errors = vec![];
+    if cups.len() < 5 {
+        errors.push(ParseError::at(input, input, "at least 5 cups"));
+    }
+    let n = cups.len() as u32;
+    let mut seen = HashMap::new();
+    // Every label is a single ASCII digit, so the cups are at the same indices in the input
+    for (i, &label) in cups.iter().enumerate() {
+        if !(1..=n).contains(&label) {
+            let expected = format!("a label from 1 to {}", n);
+            errors.push(ParseError::at_char(input, i, expected));
+        }
+        if let Some(first) = seen.insert(label, i + 1) {
+            let expected = format!("a label other than that of cup {}", first);
+            errors.push(ParseError::at_char(input, i, expected));
+        }
+    }
+    errors
+}
+
+fn play_round(cups: &mut Cups, mv: usize, trace: &mut impl Trace) {
+    let largest_cup_label = cups.len() as u32;
+    // The current cup is at the head of the queue
+    let current = cups.head();
+    // Pick up three cups after the current cup
+    let picked = {
+        let mut picked = [0; 3];
+        for picked_label in picked.iter_mut() {
+            let label = cups.get_next(current);
+            *picked_label = label;
+            cups.remove(current, label);
+        }
+        picked
+    };
+    // Select the destination cup
+    let destination_label = {
+        // The crab selects a destination cup: the cup with a label equal
+        // to the current cup's label minus one.
+        let mut label = current - 1;
+        loop {
+            if label == 0 {
+                // If at any point in this process the value goes below the lowest value
+                // on any cup's label, it wraps around to the highest value on any cup's label
+                // instead.
+                label = largest_cup_label;
+            }
+            if picked.contains(&label) {
+                // If this would select one of the cups that was just picked up,
+                // the crab will keep subtracting one until it finds a cup that wasn't
+                // just picked up.
+                label -= 1;
+            } else {
+                break label;
+            }
+        }
+    };
+    // The crab places the cups it just picked up so that they are immediately clockwise
+    // of the destination cup.
+    for &label in picked.iter().rev() {
+        cups.insert_after(destination_label, label);
+    }
+    trace.record(|| {
+        let picked = picked.map(|label| label.to_string()).join(",");
+        TraceEvent::new("move")
+            .with("move", mv)
+            .with("current", current)
+            .with("picked", picked)
+            .with("destination", destination_label)
+    });
+    // The crab selects a new current cup: the cup which is immediately clockwise
+    // of the current cup.
+    cups.advance_head();
+}
+
+fn run_part1(input: &[u32], trace: &mut impl Trace) -> String {
+    let mut cups = input.iter().copied().collect::<Cups>();
+    // Play 100 rounds
+    for i in 1..=100 {
+        play_round(&mut cups, i, trace);
+    }
+    // Rotate cup with label 1 into first position
+    cups.set_head(1);
+    cups.into_vec()
+        .into_iter()
+        .skip(1) // drop first cup (with label 1)
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("") // join without extra characters
+}
+
+#[aoc(day23, part1)]
+pub fn part1(input: &[u32]) -> String {
+    run_part1(input, &mut NoTrace)
+}
+
+fn run_part2(input: &[u32], trace: &mut impl Trace) -> u64 {
+    let largest_cup_label = input.len() as u32;
+    let mut input = input.to_vec();
+    input.extend((largest_cup_label + 1)..=1_000_000);
+
+    let mut cups = input.into_iter().collect::<Cups>();
+    for i in 1..=10_000_000 {
+        play_round(&mut cups, i, trace);
+    }
+
+    // Multiply next two cups after cup with label 1
+    let next1 = cups.get_next(1);
+    let next2 = cups.get_next(next1);
+    (next1 as u64) * (next2 as u64)
+}
+
+#[aoc(day23, part2)]
+pub fn part2(input: &[u32]) -> u64 {
+    run_part2(input, &mut NoTrace)
+}
+
+/// Runs a part with tracing: every move is a `move` event with the current cup, the picked up
+/// cups and the destination cup. Part 2 has ten million moves, so its trace is large.
+pub fn trace(input: &str, part: u8, trace: &mut impl Trace) -> Result<Answer, Box<dyn Error>> {
+    let cups = input_generator(input)?;
+    Ok(match part {
+        1 => run_part1(&cups, trace).into(),
+        2 => run_part2(&cups, trace).into(),
+        part => return Err(format!("no part {}", part).into()),
+    })
+}
+
+/// Checks that the cups are labeled 1 to n, each label once, with at least 5 cups
+/// (the current cup, three cups to pick up and a destination cup).
+pub fn validate(input: &str) -> Result<Vec<String>, ParseError> {
+    let input = &normalize(input);
+    let cups = parse_labels(input).map_err(|e| e.in_day(23))?;
+    Ok(label_errors(input, &cups)
+        .iter()
+        .map(ToString::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day23::{input_generator, part1, part2, validate};
+
+    const EXAMPLE: &str = "389125467";
+
+    #[test]
+    fn generator() {
+        assert_eq!(
+            input_generator(EXAMPLE).unwrap(),
+            vec![3, 8, 9, 1, 2, 5, 4, 6, 7]
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), "67384529");
+    }
+
+    /// Plays 10 million rounds, run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 149245887792);
+    }
+
+    #[test]
+    fn validation() {
+        assert!(validate("389125467").unwrap().is_empty());
+        assert_eq!(
+            validate("3891").unwrap(),
+            vec![
+                "line 1, column 1: expected at least 5 cups, found `3891`",
+                "line 1, column 2: expected a label from 1 to 4, found `8`",
+                "line 1, column 3: expected a label from 1 to 4, found `9`",
+            ]
+        );
+        assert_eq!(
+            validate("389125401").unwrap(),
+            vec![
+                "line 1, column 8: expected a label from 1 to 9, found `0`",
+                "line 1, column 9: expected a label other than that of cup 4, found `1`",
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_labels() {
+        let error = |input| input_generator(input).unwrap_err().to_string();
+        assert_eq!(
+            error("3891254670"),
+            "day 23, line 1, column 10: expected a label from 1 to 10, found `0`"
+        );
+        assert_eq!(
+            error("12"),
+            "day 23, line 1, column 1: expected at least 5 cups, found `12`"
+        );
+        assert!(error("").starts_with("day 23"));
+    }
+}
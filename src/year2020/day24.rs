@@ -0,0 +1,271 @@
+use std::collections::HashSet;
+use std::error::Error;
+
+use crate::util::{
+    hex, normalize, parse_lines, Automaton, Frames, Grid, NoTrace, Palette, ParseError, Trace,
+    TraceEvent, Vector2D,
+};
+use crate::Answer;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Direction {
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE,
+}
+
+impl Direction {
+    /// ```text
+    ///                 +y
+    ///         NW     NE
+    ///           \   /
+    ///            \ /
+    /// -x  W ------.-----E  +x
+    ///            / \
+    ///           /   \
+    ///         SW     SE
+    ///        -y
+    ///
+    ///  E = ( 1,  0)
+    ///  W = (-1,  0)
+    /// NE = ( 0,  1)
+    /// SW = ( 0, -1)
+    /// NW = (-1,  1) = W + NE
+    /// SE = ( 1, -1) = E + SW
+    /// ```
+    fn step(&self) -> Vector2D {
+        match self {
+            Direction::E => Vector2D::new(1, 0),
+            Direction::W => Vector2D::new(-1, 0),
+            Direction::NE => Vector2D::new(0, 1),
+            Direction::SW => Vector2D::new(0, -1),
+            Direction::NW => Vector2D::new(-1, 1),
+            Direction::SE => Vector2D::new(1, -1),
+        }
+    }
+}
+
+type Path = Vec<Direction>;
+
+fn parse_path(line: &str) -> Result<Path, ParseError> {
+    let mut chars = line.char_indices();
+    let mut path = vec![];
+    while let Some((i, first)) = chars.next() {
+        path.push(match first {
+            'e' => Direction::E,
+            'w' => Direction::W,
+            's' | 'n' => match (first, chars.next()) {
+                ('s', Some((_, 'e'))) => Direction::SE,
+                ('s', Some((_, 'w'))) => Direction::SW,
+                ('n', Some((_, 'e'))) => Direction::NE,
+                ('n', Some((_, 'w'))) => Direction::NW,
+                (_, second) => {
+                    let end = second.map_or(line.len(), |(j, c)| j + c.len_utf8());
+                    return Err(ParseError::at(
+                        line,
+                        &line[i..end],
+                        "`se`, `sw`, `nw` or `ne`",
+                    ));
+                }
+            },
+            _ => {
+                return Err(ParseError::at_char(
+                    line,
+                    i,
+                    "`e`, `se`, `sw`, `w`, `nw` or `ne`",
+                ))
+            }
+        });
+    }
+    Ok(path)
+}
+
+#[aoc_generator(day24)]
+pub fn input_generator(input: &str) -> Result<Vec<Path>, ParseError> {
+    let input = &normalize(input);
+    parse_lines(input, parse_path).map_err(|e| e.in_day(24))
+}
+
+type TileFloor = HashSet<Vector2D>;
+
+fn flip_event(tile: Vector2D, black: bool) -> TraceEvent {
+    TraceEvent::new("flip")
+        .with("x", tile.x())
+        .with("y", tile.y())
+        .with("tile", if black { "black" } else { "white" })
+}
+
+fn create_tile_floor(paths: &[Path], trace: &mut impl Trace) -> TileFloor {
+    let mut black_tiles = TileFloor::new();
+    for (i, path) in paths.iter().enumerate() {
+        let tile = path
+            .iter()
+            .fold(Vector2D::zero(), |pos, direction| pos + direction.step());
+        if black_tiles.contains(&tile) {
+            // Flip back to white
+            black_tiles.remove(&tile);
+        } else {
+            // Flip to black
+            black_tiles.insert(tile);
+        }
+        trace.record(|| flip_event(tile, black_tiles.contains(&tile)).with("path", i + 1));
+    }
+    black_tiles
+}
+
+#[aoc(day24, part1)]
+pub fn part1(paths: &[Path]) -> usize {
+    create_tile_floor(paths, &mut NoTrace).len()
+}
+
+fn automaton() -> Automaton<Vector2D, impl Fn(Vector2D) -> Vec<Vector2D>> {
+    // Any black tile with zero or more than 2 black tiles immediately adjacent to it
+    // is flipped to white. Any white tile with exactly 2 black tiles immediately adjacent
+    // to it is flipped to black.
+    Automaton::new("B2/S12".parse().unwrap(), hex())
+}
+
+fn run_part2(paths: &[Path], trace: &mut impl Trace) -> usize {
+    let automaton = automaton();
+    let mut black_tiles = create_tile_floor(paths, trace);
+    for day in 1..=100 {
+        let next = automaton.step(&black_tiles);
+        // Sort the flipped tiles by coordinate, so the trace is the same on every run
+        let mut flipped = next.symmetric_difference(&black_tiles).collect::<Vec<_>>();
+        flipped.sort_by_key(|tile| (tile.x(), tile.y()));
+        for &tile in flipped {
+            trace.record(|| flip_event(tile, next.contains(&tile)).with("day", day));
+        }
+        black_tiles = next;
+    }
+    black_tiles.len()
+}
+
+#[aoc(day24, part2)]
+pub fn part2(paths: &[Path]) -> usize {
+    run_part2(paths, &mut NoTrace)
+}
+
+/// Runs a part with tracing: every tile which is flipped by a path or on a day
+/// is a `flip` event.
+pub fn trace(input: &str, part: u8, trace: &mut impl Trace) -> Result<Answer, Box<dyn Error>> {
+    let paths = input_generator(input)?;
+    let black_tiles = match part {
+        1 => create_tile_floor(&paths, trace).len(),
+        2 => run_part2(&paths, trace),
+        part => return Err(format!("no part {}", part).into()),
+    };
+    Ok(black_tiles.into())
+}
+
+/// Renders the floor on every day, as `part2-NNNN`.
+///
+/// Every hexagonal tile is two cells wide, and every row is shifted by one cell,
+/// so that each tile touches its six neighbours. White and black tiles are colors 0 and 1
+/// of the palette.
+pub fn render(input: &str, frames: &mut Frames) -> Result<(), Box<dyn Error>> {
+    let paths = input_generator(input)?;
+    let automaton = automaton();
+    let mut states = vec![create_tile_floor(&paths, &mut NoTrace)];
+    for day in 0..100 {
+        states.push(automaton.step(&states[day]));
+    }
+
+    // Use the same bounds for all frames
+    let cell = |tile: &Vector2D| (2 * tile.x() + tile.y(), -tile.y());
+    let cells = states.iter().flatten().map(cell);
+    let (min_x, max_x, min_y, max_y) = cells.fold(
+        (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
+        |(min_x, max_x, min_y, max_y), (x, y)| {
+            (min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y))
+        },
+    );
+    if min_x > max_x {
+        return Ok(());
+    }
+    let width = (max_x - min_x + 2) as usize;
+    let height = (max_y - min_y + 1) as usize;
+
+    let palette = Palette(vec![[240, 236, 224], [32, 32, 32]]);
+    for state in &states {
+        let mut frame = Grid::filled(width, height, 0);
+        for tile in state {
+            let (x, y) = cell(tile);
+            let pos = Vector2D::new(x - min_x, y - min_y);
+            frame[pos] = 1;
+            frame[pos + Vector2D::new(1, 0)] = 1;
+        }
+        frames.write("part2", &frame, &palette)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::TraceEvent;
+    use crate::year2020::day24::{input_generator, part1, part2, trace, Direction};
+    use crate::Answer;
+
+    const EXAMPLE: &str = "\
+sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew";
+
+    #[test]
+    fn generator() {
+        let paths = input_generator("esenee\nnwwswee").unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                vec![Direction::E, Direction::SE, Direction::NE, Direction::E],
+                vec![
+                    Direction::NW,
+                    Direction::W,
+                    Direction::SW,
+                    Direction::E,
+                    Direction::E
+                ]
+            ]
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 10);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 2208);
+    }
+
+    #[test]
+    fn traced_in_order() {
+        let trace_events = || {
+            let mut events = Vec::<TraceEvent>::new();
+            assert_eq!(trace(EXAMPLE, 2, &mut events).unwrap(), Answer::from(2208));
+            events
+        };
+        assert_eq!(trace_events(), trace_events());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::util::{discrete_log, mod_pow, normalize, parse_lines, parse_token, ParseError};
+
+/// Parses the public keys of the card and the door, one per line.
+#[aoc_generator(day25)]
+pub fn input_generator(input: &str) -> Result<(u64, u64), ParseError> {
+    let input = &normalize(input);
+    let keys =
+        parse_lines(input, |line| parse_token(line, line, "integer")).map_err(|e| e.in_day(25))?;
+    match keys[..] {
+        [card_key, door_key] => Ok((card_key, door_key)),
+        [_, _, ..] => {
+            let extra_line = input.lines().nth(2).unwrap();
+            Err(ParseError::at(input, extra_line, "end of input").in_day(25))
+        }
+        _ => Err(ParseError::at(input, &input[input.len()..], "two public keys").in_day(25)),
+    }
+}
+
+const MODULUS: u64 = 20201227;
+
+fn transform(subject_number: u64, loop_size: u64) -> u64 {
+    mod_pow(subject_number, loop_size, MODULUS)
+}
+
+fn crack_loop_size(key: u64, subject_number: u64) -> u64 {
+    discrete_log(subject_number, key, MODULUS).expect("no loop size for key")
+}
+
+#[aoc(day25, part1)]
+pub fn part1(&(card_key, door_key): &(u64, u64)) -> u64 {
+    let card_loop_size = crack_loop_size(card_key, 7);
+    let door_loop_size = crack_loop_size(door_key, 7);
+    assert_eq!(transform(7, card_loop_size), card_key);
+    assert_eq!(transform(7, door_loop_size), door_key);
+
+    let card_encryption_key = transform(door_key, card_loop_size);
+    let door_encryption_key = transform(card_key, door_loop_size);
+    assert_eq!(card_encryption_key, door_encryption_key);
+
+    card_encryption_key
+}
+
+/// Checks that both public keys are a transformation of the subject number 7,
+/// which needs them to be below the modulus.
+pub fn validate(input: &str) -> Result<Vec<String>, ParseError> {
+    let (card_key, door_key) = input_generator(input)?;
+    let keys = [card_key, door_key];
+    let violations = keys
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &key)| {
+            if key == 0 || key >= MODULUS {
+                Some(format!(
+                    "line {}: public key {} is not from 1 to {}",
+                    i + 1,
+                    key,
+                    MODULUS - 1
+                ))
+            } else if discrete_log(7, key, MODULUS).is_none() {
+                Some(format!(
+                    "line {}: no loop size transforms 7 into public key {}",
+                    i + 1,
+                    key
+                ))
+            } else {
+                None
+            }
+        })
+        .collect();
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day25::{crack_loop_size, input_generator, part1, validate};
+
+    const EXAMPLE: &str = "\
+5764801
+17807724";
+
+    #[test]
+    fn generator() {
+        assert_eq!(input_generator(EXAMPLE).unwrap(), (5764801, 17807724));
+        assert_eq!(
+            input_generator("5764801").unwrap_err().to_string(),
+            "day 25, line 1, column 8: expected two public keys, found end of input"
+        );
+        assert_eq!(
+            input_generator("5764801\n17807724\n1")
+                .unwrap_err()
+                .to_string(),
+            "day 25, line 3, column 1: expected end of input, found `1`"
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(crack_loop_size(input.0, 7), 8);
+        assert_eq!(crack_loop_size(input.1, 7), 11);
+        assert_eq!(part1(&input), 14897079);
+    }
+
+    #[test]
+    fn validation() {
+        assert!(validate(EXAMPLE).unwrap().is_empty());
+        assert_eq!(
+            validate("0\n20201227").unwrap(),
+            vec![
+                "line 1: public key 0 is not from 1 to 20201226",
+                "line 2: public key 20201227 is not from 1 to 20201226",
+            ]
+        );
+    }
+}
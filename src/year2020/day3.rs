@@ -0,0 +1,203 @@
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+use std::iter;
+use std::ops::RangeInclusive;
+
+use crate::util::{normalize, Grid, ParseError, Vector2D};
+
+#[derive(Debug)]
+pub struct Map {
+    trees: Grid<bool>,
+}
+
+/// The steps of a toboggan: `right` (or left, if negative) and `down` on every step.
+///
+/// Steps of `right 1, down 2` give a slope of one half, and visit every other row.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Slope {
+    right: i32,
+    down: i32,
+}
+
+impl Slope {
+    /// Returns `None` if the steps don't go down, since the toboggan would never reach
+    /// the bottom of the map.
+    pub fn new(right: i32, down: i32) -> Option<Slope> {
+        if down > 0 {
+            Some(Slope { right, down })
+        } else {
+            None
+        }
+    }
+
+    pub fn right(&self) -> i32 {
+        self.right
+    }
+
+    pub fn down(&self) -> i32 {
+        self.down
+    }
+}
+
+impl Display for Slope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "right {}, down {}", self.right, self.down)
+    }
+}
+
+/// A cell of the map visited by a toboggan.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Visit {
+    /// The position in the map, with the x coordinate wrapped around its width.
+    pub position: Vector2D,
+    pub tree: bool,
+}
+
+impl Map {
+    /// Returns the cells visited on the given slope, from the top left until the bottom of the map.
+    pub fn path(&self, slope: Slope) -> impl Iterator<Item = Visit> + '_ {
+        let step = Vector2D::new(slope.right, slope.down);
+        iter::successors(Some(Vector2D::zero()), move |&pos| Some(pos + step))
+            .take_while(move |pos| pos.y() < self.trees.height() as i32)
+            .map(move |pos| {
+                let position =
+                    Vector2D::new(pos.x().rem_euclid(self.trees.width() as i32), pos.y());
+                Visit {
+                    position,
+                    tree: self.trees[position],
+                }
+            })
+    }
+
+    pub fn trees_on_slope(&self, slope: Slope) -> usize {
+        self.path(slope).filter(|visit| visit.tree).count()
+    }
+
+    /// Returns the slope which hits the fewest trees, and its number of trees, out of all slopes
+    /// with steps in the given ranges. Ties are broken by the smallest steps down, then right.
+    /// Returns `None` if no slope in the ranges goes down.
+    pub fn fewest_trees(
+        &self,
+        rights: RangeInclusive<i32>,
+        downs: RangeInclusive<i32>,
+    ) -> Option<(Slope, usize)> {
+        self.trees_on_slopes(rights, downs)
+            .min_by_key(|&(_, trees)| trees)
+    }
+
+    /// Like [`Map::fewest_trees`], but returns the slope which hits the most trees.
+    pub fn most_trees(
+        &self,
+        rights: RangeInclusive<i32>,
+        downs: RangeInclusive<i32>,
+    ) -> Option<(Slope, usize)> {
+        self.trees_on_slopes(rights, downs)
+            .min_by_key(|&(_, trees)| Reverse(trees))
+    }
+
+    fn trees_on_slopes(
+        &self,
+        rights: RangeInclusive<i32>,
+        downs: RangeInclusive<i32>,
+    ) -> impl Iterator<Item = (Slope, usize)> + '_ {
+        downs
+            .flat_map(move |down| {
+                rights
+                    .clone()
+                    .filter_map(move |right| Slope::new(right, down))
+            })
+            .map(move |slope| (slope, self.trees_on_slope(slope)))
+    }
+}
+
+#[aoc_generator(day3)]
+pub fn input_generator(input: &str) -> Result<Map, ParseError> {
+    let input = &normalize(input);
+    let trees = Grid::parse(input, "`#` or `.`", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+    .map_err(|e| e.in_day(3))?;
+    Ok(Map { trees })
+}
+
+#[aoc(day3, part1)]
+pub fn part1(input: &Map) -> usize {
+    input.trees_on_slope(Slope::new(3, 1).unwrap())
+}
+
+#[aoc(day3, part2)]
+pub fn part2(input: &Map) -> usize {
+    let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+    slopes
+        .iter()
+        .map(|&(right, down)| input.trees_on_slope(Slope::new(right, down).unwrap()))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::Vector2D;
+    use crate::year2020::day3::*;
+
+    const EXAMPLE: &str = "\
+..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    #[test]
+    fn generator() {
+        let map = input_generator(EXAMPLE).unwrap();
+        assert_eq!((map.trees.width(), map.trees.height()), (11, 11));
+        assert_eq!(map.trees.values().filter(|&&tree| tree).count(), 37);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 7);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 336);
+    }
+
+    #[test]
+    fn paths() {
+        let map = input_generator("#..\n..#\n.#.\n").unwrap();
+        let path = map.path(Slope::new(-1, 1).unwrap()).collect::<Vec<_>>();
+        let positions = path.iter().map(|visit| visit.position).collect::<Vec<_>>();
+        let expected = [(0, 0), (2, 1), (1, 2)];
+        let expected = expected.iter().map(|&(x, y)| Vector2D::new(x, y));
+        assert_eq!(positions, expected.collect::<Vec<_>>());
+        assert!(path.iter().all(|visit| visit.tree));
+        assert_eq!(map.trees_on_slope(Slope::new(1, 1).unwrap()), 1);
+        assert_eq!(map.trees_on_slope(Slope::new(4, 2).unwrap()), 2);
+        assert_eq!(Slope::new(1, 0), None);
+    }
+
+    #[test]
+    fn slope_search() {
+        let map = input_generator(EXAMPLE).unwrap();
+        let (slope, trees) = map.most_trees(0..=10, 1..=3).unwrap();
+        assert_eq!(
+            (slope.to_string(), trees),
+            ("right 3, down 1".to_owned(), 7)
+        );
+        let (slope, trees) = map.fewest_trees(0..=10, -1..=1).unwrap();
+        assert_eq!(
+            (slope.to_string(), trees),
+            ("right 2, down 1".to_owned(), 1)
+        );
+        assert_eq!(map.fewest_trees(0..=10, -1..=0), None);
+    }
+}
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use crate::util::{
+    blocks, lines, literal, normalize, parse, separated, take_while1, word, ParseError, Parser,
+};
+
 #[derive(Debug)]
 pub struct Passport {
     fields: HashMap<String, String>,
@@ -39,20 +43,24 @@ impl Passport {
     }
 }
 
+/// Parses the `key:value` fields of a passport, separated by spaces or newlines.
+fn passport<'a>() -> impl Parser<'a, Passport> {
+    let key = take_while1("field name", |c| c.is_ascii_alphanumeric());
+    let field = key.skip(literal(":")).then(word());
+    lines(separated(field, literal(" "))).map(|lines| Passport {
+        fields: lines
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect(),
+    })
+}
+
 impl FromStr for Passport {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let fields = s
-            .split(&[' ', '\n'][..])
-            .map(|field| {
-                let mut parts = field.split(':');
-                let key = parts.next().unwrap().to_owned();
-                let value = parts.next().unwrap().to_owned();
-                (key, value)
-            })
-            .collect::<HashMap<String, String>>();
-        Ok(Passport { fields })
+        parse(s, passport())
     }
 }
 
@@ -82,11 +90,9 @@ fn is_password_id(s: &str) -> bool {
 }
 
 #[aoc_generator(day4)]
-pub fn input_generator(input: &str) -> Vec<Passport> {
-    input
-        .split("\n\n")
-        .map(|x| Passport::from_str(x).unwrap())
-        .collect()
+pub fn input_generator(input: &str) -> Result<Vec<Passport>, ParseError> {
+    let input = &normalize(input);
+    parse(input, blocks(passport())).map_err(|e| e.in_day(4))
 }
 
 #[aoc(day4, part1)]
@@ -109,7 +115,41 @@ pub fn part2(passports: &[Passport]) -> usize {
 mod tests {
     use std::str::FromStr;
 
-    use crate::day4::Passport;
+    use crate::year2020::day4::{input_generator, part1, part2, Passport};
+
+    const EXAMPLE: &str = "\
+ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+
+    #[test]
+    fn generator() {
+        let passports = input_generator(EXAMPLE).unwrap();
+        assert_eq!(passports.len(), 4);
+        assert_eq!(passports[0].fields.len(), 8);
+        assert_eq!(passports[0].fields["hgt"], "183cm");
+        assert_eq!(passports[3].fields.len(), 6);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 2);
+    }
 
     #[test]
     fn part1_valid() {
@@ -119,7 +159,7 @@ mod tests {
         ];
         for input in inputs {
             let passport = Passport::from_str(input).unwrap();
-            assert_eq!(true, passport.is_valid_part1())
+            assert!(passport.is_valid_part1())
         }
     }
 
@@ -131,7 +171,7 @@ mod tests {
         ];
         for input in inputs {
             let passport = Passport::from_str(input).unwrap();
-            assert_eq!(false, passport.is_valid_part1())
+            assert!(!passport.is_valid_part1())
         }
     }
 
@@ -145,7 +185,7 @@ mod tests {
         ];
         for input in inputs {
             let passport = Passport::from_str(input).unwrap();
-            assert_eq!(true, passport.is_valid_part2())
+            assert!(passport.is_valid_part2())
         }
     }
 
@@ -159,7 +199,7 @@ mod tests {
         ];
         for input in inputs {
             let passport = Passport::from_str(input).unwrap();
-            assert_eq!(false, passport.is_valid_part2())
+            assert!(!passport.is_valid_part2())
         }
     }
 }
@@ -0,0 +1,125 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::util::{normalize, parse_lines, ParseError};
+
+#[aoc_generator(day5)]
+pub fn input_generator(input: &str) -> Result<Vec<i32>, ParseError> {
+    let input = &normalize(input);
+    parse_lines(input, parse_seat_id).map_err(|e| e.in_day(5))
+}
+
+fn parse_seat_id(seat: &str) -> Result<i32, ParseError> {
+    seat.char_indices().try_fold(0, |id, (i, c)| {
+        let bit = match c {
+            'F' | 'L' => 0,
+            'B' | 'R' => 1,
+            _ => return Err(ParseError::at_char(seat, i, "`F`, `B`, `L` or `R`")),
+        };
+        Ok((id << 1) | bit)
+    })
+}
+
+#[aoc(day5, part1)]
+pub fn part1(input: &[i32]) -> i32 {
+    *input.iter().max().unwrap()
+}
+
+/// Checks that every boarding pass has 7 `F`/`B` followed by 3 `L`/`R`, that no seat is taken
+/// twice, and that exactly one seat is free between two taken seats.
+pub fn validate(input: &str) -> Result<Vec<String>, ParseError> {
+    let seats = input_generator(input)?;
+    let mut violations = vec![];
+    let input = normalize(input);
+    let mut lines = HashMap::new();
+    for (i, (pass, &seat)) in input.lines().zip(&seats).enumerate() {
+        let (row, column) = pass.split_at(pass.len().min(7));
+        if row.len() != 7
+            || column.len() != 3
+            || row.contains(['L', 'R'])
+            || column.contains(['F', 'B'])
+        {
+            violations.push(format!(
+                "line {}: boarding pass `{}` is not 7 `F`/`B` followed by 3 `L`/`R`",
+                i + 1,
+                pass
+            ));
+        }
+        if let Some(first) = lines.insert(seat, i + 1) {
+            violations.push(format!(
+                "line {}: seat {} is also taken on line {}",
+                i + 1,
+                seat,
+                first
+            ));
+        }
+    }
+    let free = match (seats.iter().min(), seats.iter().max()) {
+        (Some(&min), Some(&max)) => (min + 1..max)
+            .filter(|seat| !lines.contains_key(seat))
+            .filter(|seat| lines.contains_key(&(seat - 1)) && lines.contains_key(&(seat + 1)))
+            .collect(),
+        _ => vec![],
+    };
+    if free.len() != 1 {
+        violations.push(format!(
+            "expected exactly one free seat between taken seats, found {:?}",
+            free
+        ));
+    }
+    Ok(violations)
+}
+
+#[aoc(day5, part2)]
+pub fn part2(input: &[i32]) -> i32 {
+    let seats = input.iter().copied().collect::<HashSet<i32>>();
+    let min_seat = seats.iter().min().unwrap();
+    let max_seat = seats.iter().max().unwrap();
+    for seat in (min_seat + 1)..=(max_seat - 1) {
+        if !seats.contains(&seat) && seats.contains(&(seat - 1)) && seats.contains(&(seat + 1)) {
+            return seat;
+        }
+    }
+    panic!("seat not found")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day5::{input_generator, part1, part2, validate};
+
+    const EXAMPLE: &str = "\
+FBFBBFFRLR
+BFFFBBFRRR
+FFFBBBFRRR
+BBFFBBFRLL";
+
+    #[test]
+    fn generator() {
+        assert_eq!(input_generator(EXAMPLE).unwrap(), vec![357, 567, 119, 820]);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 820);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&[10, 8, 11, 7]), 9);
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(
+            validate("FFFFFFFLLL\nFFFFFFFLRL").unwrap(),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            validate("FFFFFFFLLL\nFFFFFFFLLL\nFFFFFFRLL").unwrap(),
+            vec![
+                "line 2: seat 0 is also taken on line 1",
+                "line 3: boarding pass `FFFFFFRLL` is not 7 `F`/`B` followed by 3 `L`/`R`",
+                "expected exactly one free seat between taken seats, found []",
+            ]
+        );
+    }
+}
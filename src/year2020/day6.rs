@@ -0,0 +1,89 @@
+use std::collections::HashSet;
+
+use crate::util::{normalize, parse_blocks, parse_lines, ParseError};
+
+#[aoc_generator(day6)]
+pub fn input_generator(input: &str) -> Result<Vec<Vec<Vec<char>>>, ParseError> {
+    let input = &normalize(input);
+    parse_blocks(input, |group| {
+        parse_lines(group, |person| {
+            person
+                .char_indices()
+                .map(|(i, c)| {
+                    if c.is_ascii_lowercase() {
+                        Ok(c)
+                    } else {
+                        Err(ParseError::at_char(person, i, "a question from `a` to `z`"))
+                    }
+                })
+                .collect()
+        })
+    })
+    .map_err(|e| e.in_day(6))
+}
+
+#[aoc(day6, part1)]
+pub fn part1(groups: &[Vec<Vec<char>>]) -> usize {
+    let group_counts = groups.iter().map(|group| {
+        let answers = group.iter().flatten().collect::<HashSet<_>>();
+        answers.len()
+    });
+    group_counts.sum()
+}
+
+#[aoc(day6, part2)]
+pub fn part2(groups: &[Vec<Vec<char>>]) -> usize {
+    let group_counts = groups.iter().map(|group| {
+        let answers = group
+            .iter()
+            .map(|person| person.iter().cloned().collect::<HashSet<char>>())
+            .fold(None, |left: Option<HashSet<char>>, right| {
+                Some(match left {
+                    None => right,
+                    Some(left) => left.intersection(&right).cloned().collect(),
+                })
+            })
+            .unwrap();
+        answers.len()
+    });
+    group_counts.sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day6::{input_generator, part1, part2};
+
+    const EXAMPLE: &str = "\
+abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+    #[test]
+    fn generator() {
+        let groups = input_generator(EXAMPLE).unwrap();
+        assert_eq!(groups.len(), 5);
+        assert_eq!(groups[2], vec![vec!['a', 'b'], vec!['a', 'c']]);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 11);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 6);
+    }
+}
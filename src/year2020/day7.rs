@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use crate::util::{
+    dfs, identifier, integer, lines, literal, memoize, normalize, parse, separated, ParseError,
+    Parser,
+};
+
+#[derive(Debug)]
+pub struct Rule {
+    color: String,
+    contents: Vec<(usize, String)>,
+}
+
+/// Parses a color of two words, like `shiny gold`.
+fn color<'a>() -> impl Parser<'a, String> {
+    identifier()
+        .skip(literal(" "))
+        .then(identifier())
+        .map(|(adjective, color)| format!("{} {}", adjective, color))
+}
+
+/// Parses a rule like `light red bags contain 1 bright white bag, 2 muted yellow bags.`
+fn rule<'a>() -> impl Parser<'a, Rule> {
+    let bags = literal(" bags").or(literal(" bag"));
+    let content = integer().skip(literal(" ")).then(color()).skip(bags);
+    let contents = literal("no other bags")
+        .map(|_| vec![])
+        .or(separated(content, literal(", ")));
+    color()
+        .skip(literal(" bags contain "))
+        .then(contents)
+        .skip(literal("."))
+        .map(|(color, contents)| Rule { color, contents })
+}
+
+#[aoc_generator(day7)]
+pub fn input_generator(input: &str) -> Result<Vec<Rule>, ParseError> {
+    let input = &normalize(input);
+    parse(input, lines(rule())).map_err(|e| e.in_day(7))
+}
+
+#[aoc(day7, part1)]
+pub fn part1(rules: &[Rule]) -> usize {
+    let mut containers = HashMap::<&str, Vec<&str>>::new();
+    for rule in rules {
+        for (_, color) in &rule.contents {
+            containers.entry(color).or_default().push(&rule.color);
+        }
+    }
+    let bags = dfs(Some("shiny gold"), |color| {
+        containers.get(color).cloned().unwrap_or_default()
+    });
+    bags.len() - 1 // minus the shiny gold bag itself
+}
+
+#[aoc(day7, part2)]
+pub fn part2(rules: &[Rule]) -> usize {
+    let rules = rules
+        .iter()
+        .map(|rule| (rule.color.as_str(), &rule.contents))
+        .collect::<HashMap<_, _>>();
+    let count = memoize("shiny gold", |count_bags, color| {
+        let contents = rules.get(color).expect("no rule for color");
+        contents
+            .iter()
+            .map(|(amount, color)| amount * count_bags(color))
+            .sum::<usize>()
+            + 1
+    });
+    count - 1 // minus the outer bag itself
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day7::{input_generator, part1, part2};
+
+    const EXAMPLE: &str = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+    const EXAMPLE_2: &str = "\
+shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+    #[test]
+    fn generator() {
+        let rules = input_generator(EXAMPLE).unwrap();
+        assert_eq!(rules.len(), 9);
+        assert_eq!(rules[0].color, "light red");
+        assert_eq!(
+            rules[0].contents,
+            vec![
+                (1, "bright white".to_owned()),
+                (2, "muted yellow".to_owned())
+            ]
+        );
+        assert_eq!(rules[7].contents, vec![]);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 4);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 32);
+        assert_eq!(part2(&input_generator(EXAMPLE_2).unwrap()), 126);
+    }
+}
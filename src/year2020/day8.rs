@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::util::{
+    normalize, parse_lines, parse_token, split_pair, NoTrace, ParseError, Trace, TraceEvent,
+};
+use crate::Answer;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instruction {
+    Acc(i32),
+    Jmp(i32),
+    Nop(i32),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (op, arg) = split_pair(s, s, " ")?;
+        let arg = parse_token::<i32>(s, arg, "integer")?;
+        Ok(match op {
+            "acc" => Instruction::Acc(arg),
+            "jmp" => Instruction::Jmp(arg),
+            "nop" => Instruction::Nop(arg),
+            _ => return Err(ParseError::at(s, op, "`acc`, `jmp` or `nop`")),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Machine {
+    program: Vec<Instruction>,
+    pc: usize,
+    acc: i32,
+}
+
+impl Machine {
+    fn new(program: Vec<Instruction>) -> Self {
+        Self {
+            program,
+            pc: 0,
+            acc: 0,
+        }
+    }
+
+    fn acc(&self) -> i32 {
+        self.acc
+    }
+
+    fn pc(&self) -> usize {
+        self.pc
+    }
+
+    fn step(&mut self, trace: &mut impl Trace) {
+        let (pc, instruction) = (self.pc, self.program[self.pc]);
+        match instruction {
+            Instruction::Acc(arg) => {
+                self.acc += arg;
+                self.pc += 1;
+            }
+            Instruction::Jmp(offset) => {
+                self.pc = ((self.pc as isize) + (offset as isize)) as usize;
+            }
+            Instruction::Nop(_) => {
+                self.pc += 1;
+            }
+        }
+        trace.record(|| {
+            let (op, arg) = match instruction {
+                Instruction::Acc(arg) => ("acc", arg),
+                Instruction::Jmp(arg) => ("jmp", arg),
+                Instruction::Nop(arg) => ("nop", arg),
+            };
+            TraceEvent::new("exec")
+                .with("pc", pc)
+                .with("op", op)
+                .with("arg", arg)
+                .with("acc", self.acc)
+        });
+    }
+}
+
+#[aoc_generator(day8)]
+pub fn input_generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let input = &normalize(input);
+    parse_lines(input, Instruction::from_str).map_err(|e| e.in_day(8))
+}
+
+fn detect_loop(program: &[Instruction], trace: &mut impl Trace) -> (bool, i32) {
+    let mut seen = HashSet::<usize>::new();
+    let mut machine = Machine::new(program.to_vec());
+    seen.insert(machine.pc());
+    while machine.pc() < program.len() {
+        machine.step(trace);
+        if !seen.insert(machine.pc()) {
+            // Already seen this instruction, loop detected
+            trace.record(|| {
+                TraceEvent::new("loop")
+                    .with("pc", machine.pc())
+                    .with("acc", machine.acc())
+            });
+            return (false, machine.acc());
+        }
+    }
+    // Halted
+    trace.record(|| TraceEvent::new("halt").with("acc", machine.acc()));
+    (true, machine.acc())
+}
+
+/// Returns the accumulator when the program loops, or `None` if it halts.
+fn run_part1(program: &[Instruction], trace: &mut impl Trace) -> Option<i32> {
+    match detect_loop(program, trace) {
+        (false, acc) => Some(acc),
+        (true, _) => None,
+    }
+}
+
+#[aoc(day8, part1)]
+pub fn part1(program: &[Instruction]) -> i32 {
+    run_part1(program, &mut NoTrace).expect("program does not loop")
+}
+
+/// Returns the accumulator of the program with a single jmp/nop swapped which halts,
+/// or `None` if no single swap makes the program halt.
+fn run_part2(program: &[Instruction], trace: &mut impl Trace) -> Option<i32> {
+    for (i, instruction) in program.iter().enumerate() {
+        // Swap jmp with nop
+        let modified_instruction = match *instruction {
+            Instruction::Jmp(arg) => Instruction::Nop(arg),
+            Instruction::Nop(arg) => Instruction::Jmp(arg),
+            Instruction::Acc(_) => continue,
+        };
+        let mut modified_program = program.to_vec();
+        modified_program[i] = modified_instruction;
+        trace.record(|| TraceEvent::new("patch").with("pc", i));
+        // Check if modified program terminates
+        if let (true, result) = detect_loop(&modified_program, trace) {
+            return Some(result);
+        }
+    }
+    None
+}
+
+#[aoc(day8, part2)]
+pub fn part2(program: &[Instruction]) -> i32 {
+    run_part2(program, &mut NoTrace).expect("no single swap terminates the program")
+}
+
+/// Checks that the program loops, that swapping a single jmp or nop makes it halt,
+/// and that no jump leaves the program before its first instruction.
+pub fn validate(input: &str) -> Result<Vec<String>, ParseError> {
+    let program = input_generator(input)?;
+    let mut violations = vec![];
+    for (pc, instruction) in program.iter().enumerate() {
+        if let Instruction::Jmp(offset) = *instruction {
+            if (pc as isize) + (offset as isize) < 0 {
+                violations.push(format!(
+                    "line {}: `jmp {:+}` jumps before the first instruction",
+                    pc + 1,
+                    offset
+                ));
+            }
+        }
+    }
+    if run_part1(&program, &mut NoTrace).is_none() {
+        violations.push("the program halts without looping".to_owned());
+    }
+    if run_part2(&program, &mut NoTrace).is_none() {
+        violations.push("no single swap terminates the program".to_owned());
+    }
+    Ok(violations)
+}
+
+/// Runs a part with tracing: every executed instruction is an `exec` event,
+/// followed by a `loop` or `halt` event. In part 2, every attempt to fix the program
+/// starts with a `patch` event for the swapped instruction.
+pub fn trace(input: &str, part: u8, trace: &mut impl Trace) -> Result<Answer, Box<dyn Error>> {
+    let program = input_generator(input)?;
+    let acc = match part {
+        1 => run_part1(&program, trace).ok_or("program does not loop")?,
+        2 => run_part2(&program, trace).ok_or("no single swap terminates the program")?,
+        part => return Err(format!("no part {}", part).into()),
+    };
+    Ok(acc.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::TraceEvent;
+    use crate::year2020::day8::{input_generator, part1, part2, trace, validate, Instruction};
+    use crate::Answer;
+
+    const EXAMPLE: &str = "\
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+    #[test]
+    fn generator() {
+        let program = input_generator(EXAMPLE).unwrap();
+        assert_eq!(program.len(), 9);
+        assert_eq!(program[0], Instruction::Nop(0));
+        assert_eq!(program[4], Instruction::Jmp(-3));
+        assert_eq!(program[5], Instruction::Acc(-99));
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&input_generator(EXAMPLE).unwrap()), 5);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&input_generator(EXAMPLE).unwrap()), 8);
+    }
+
+    #[test]
+    fn traced() {
+        let mut events = Vec::<TraceEvent>::new();
+        assert_eq!(trace(EXAMPLE, 1, &mut events).unwrap(), Answer::from(5));
+        let pcs = events
+            .iter()
+            .filter_map(|event| event.get("pc"))
+            .collect::<Vec<_>>();
+        assert_eq!(pcs, vec!["0", "1", "2", "6", "7", "3", "4", "1"]);
+        assert_eq!(events[3].to_string(), "3 exec pc=6 op=acc arg=1 acc=2");
+        assert_eq!(events.last().unwrap().kind, "loop");
+        let error = trace(EXAMPLE, 3, &mut Vec::<TraceEvent>::new()).unwrap_err();
+        assert_eq!(error.to_string(), "no part 3");
+    }
+
+    #[test]
+    fn no_terminating_swap() {
+        let program = "jmp +0\njmp +0";
+        let error = trace(program, 2, &mut Vec::<TraceEvent>::new()).unwrap_err();
+        assert_eq!(error.to_string(), "no single swap terminates the program");
+        let error = trace("acc +1", 1, &mut Vec::<TraceEvent>::new()).unwrap_err();
+        assert_eq!(error.to_string(), "program does not loop");
+    }
+
+    #[test]
+    fn validation() {
+        assert!(validate(EXAMPLE).unwrap().is_empty());
+        assert_eq!(
+            validate("jmp +0\nacc +1\njmp -5").unwrap(),
+            vec!["line 3: `jmp -5` jumps before the first instruction"]
+        );
+        assert_eq!(
+            validate("acc +1").unwrap(),
+            vec![
+                "the program halts without looping",
+                "no single swap terminates the program"
+            ]
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::ops::Range;
+
+use crate::util::{normalize, parse_lines, parse_token, ParseError};
+use crate::{Answer, Explanation};
+
+const PREAMBLE_LEN: usize = 25;
+
+#[aoc_generator(day9)]
+pub fn input_generator(input: &str) -> Result<Vec<i64>, ParseError> {
+    let input = &normalize(input);
+    parse_lines(input, |line| parse_token(line, line, "integer")).map_err(|e| e.in_day(9))
+}
+
+fn is_valid(num: i64, previous: &[i64]) -> bool {
+    previous.iter().any(|&left| {
+        let right = num - left;
+        previous.contains(&right)
+    })
+}
+
+/// The first number which is not the sum of two of the numbers in its preamble.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidNumber {
+    pub index: usize,
+    pub value: i64,
+    /// The indices of the numbers before it.
+    pub preamble: Range<usize>,
+}
+
+pub fn find_invalid_number(seq: &[i64], preamble_len: usize) -> Option<InvalidNumber> {
+    seq.windows(preamble_len + 1)
+        .position(|window| {
+            let (&num, previous) = window.split_last().unwrap();
+            !is_valid(num, previous)
+        })
+        .map(|start| InvalidNumber {
+            index: start + preamble_len,
+            value: seq[start + preamble_len],
+            preamble: start..start + preamble_len,
+        })
+}
+
+fn find_first_invalid(seq: &[i64], preamble_len: usize) -> Option<i64> {
+    find_invalid_number(seq, preamble_len).map(|invalid| invalid.value)
+}
+
+#[aoc(day9, part1)]
+pub fn part1(input: &[i64]) -> i64 {
+    find_first_invalid(input, PREAMBLE_LEN).unwrap()
+}
+
+/// Finds a contiguous range of at least two numbers which sum to `target`,
+/// and returns the indices of that range.
+pub fn find_weakness_range(input: &[i64], target: i64) -> Option<Range<usize>> {
+    let mut left = 0usize;
+    let mut right = 0usize;
+    let mut sum = 0i64;
+    loop {
+        debug_assert!(left <= right);
+        debug_assert_eq!(sum, input[left..right].iter().sum());
+        match sum.cmp(&target) {
+            Ordering::Equal if right - left >= 2 => {
+                // match!
+                return Some(left..right);
+            }
+            Ordering::Less | Ordering::Equal if right < input.len() => {
+                // sum is too small (or the range too short), add the next number
+                sum += input[right];
+                right += 1;
+            }
+            Ordering::Greater if left < right => {
+                // sum is too big, subtract the first number
+                sum -= input[left];
+                left += 1;
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Returns the sum of the smallest and largest number of a range.
+fn weakness(range: &[i64]) -> i64 {
+    range.iter().min().unwrap() + range.iter().max().unwrap()
+}
+
+/// Finds a contiguous range of at least two numbers which sum to `target`,
+/// and returns the sum of the smallest and largest number in that range.
+fn find_weakness(input: &[i64], target: i64) -> i64 {
+    let range = find_weakness_range(input, target).expect("no contiguous range found");
+    weakness(&input[range])
+}
+
+#[aoc(day9, part2)]
+pub fn part2(input: &[i64]) -> i64 {
+    find_weakness(input, part1(input))
+}
+
+/// Checks that there is a number which is not the sum of two numbers in its preamble,
+/// and a contiguous range of at least two numbers which sums to it.
+pub fn validate(input: &str) -> Result<Vec<String>, ParseError> {
+    let numbers = input_generator(input)?;
+    Ok(validate_with_preamble(&numbers, PREAMBLE_LEN))
+}
+
+fn validate_with_preamble(numbers: &[i64], preamble_len: usize) -> Vec<String> {
+    if numbers.len() <= preamble_len {
+        return vec![format!(
+            "expected more than {} numbers, found {}",
+            preamble_len,
+            numbers.len()
+        )];
+    }
+    match find_invalid_number(numbers, preamble_len) {
+        None => vec![format!(
+            "every number is the sum of two of the {} numbers before it",
+            preamble_len
+        )],
+        Some(invalid) if find_weakness_range(numbers, invalid.value).is_none() => vec![format!(
+            "no contiguous range of at least two numbers sums to {} (line {})",
+            invalid.value,
+            invalid.index + 1
+        )],
+        Some(_) => vec![],
+    }
+}
+
+fn explain_with_preamble(
+    input: &[i64],
+    part: u8,
+    preamble_len: usize,
+) -> Result<Explanation, Box<dyn Error>> {
+    let invalid = find_invalid_number(input, preamble_len).ok_or("every number is valid")?;
+    let steps = vec![format!(
+        "{} on line {} is not the sum of two of the {} numbers on lines {} to {}",
+        invalid.value,
+        invalid.index + 1,
+        preamble_len,
+        invalid.preamble.start + 1,
+        invalid.preamble.end
+    )];
+    match part {
+        1 => {
+            let mut lines = invalid
+                .preamble
+                .clone()
+                .map(|line| (line, "preamble".to_owned()))
+                .collect::<Vec<_>>();
+            lines.push((invalid.index, "invalid".to_owned()));
+            Ok(Explanation {
+                answer: invalid.value.into(),
+                steps,
+                lines,
+            })
+        }
+        2 => explain_weakness(input, &invalid, steps),
+        part => Err(format!("no part {}", part).into()),
+    }
+}
+
+/// Explains part 2: the range which sums to the invalid number, after the `steps` which
+/// explain the invalid number.
+fn explain_weakness(
+    input: &[i64],
+    invalid: &InvalidNumber,
+    mut steps: Vec<String>,
+) -> Result<Explanation, Box<dyn Error>> {
+    let range = find_weakness_range(input, invalid.value)
+        .ok_or_else(|| format!("no contiguous range sums to {}", invalid.value))?;
+    let numbers = &input[range.clone()];
+    let (min, max) = (numbers.iter().min().unwrap(), numbers.iter().max().unwrap());
+    steps.push(format!(
+        "the {} numbers on lines {} to {} sum to {}",
+        numbers.len(),
+        range.start + 1,
+        range.end,
+        invalid.value
+    ));
+    steps.push(format!(
+        "smallest + largest = {} + {} = {}",
+        min,
+        max,
+        min + max
+    ));
+    let mut lines = range
+        .map(|line| {
+            let role = match input[line] {
+                value if value == *min => "range, smallest",
+                value if value == *max => "range, largest",
+                _ => "range",
+            };
+            (line, role.to_owned())
+        })
+        .collect::<Vec<_>>();
+    if invalid.index >= lines.last().unwrap().0 {
+        lines.push((invalid.index, "invalid".to_owned()));
+    } else {
+        lines.insert(0, (invalid.index, "invalid".to_owned()));
+    }
+    Ok(Explanation {
+        answer: Answer::from(weakness(numbers)),
+        steps,
+        lines,
+    })
+}
+
+/// Explains a part: the invalid number with its preamble, and for part 2 also the range
+/// which sums to it.
+pub fn explain(input: &str, part: u8) -> Result<Explanation, Box<dyn Error>> {
+    explain_with_preamble(&input_generator(input)?, part, PREAMBLE_LEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2020::day9::{
+        explain_with_preamble, find_first_invalid, find_invalid_number, find_weakness,
+        find_weakness_range, input_generator, validate_with_preamble,
+    };
+    use crate::Answer;
+
+    const EXAMPLE: &str = "\
+35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+
+    #[test]
+    fn generator() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input.len(), 20);
+        assert_eq!(input[14], 127);
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(find_first_invalid(&input, 5), Some(127));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(find_weakness(&input, 127), 62);
+    }
+
+    #[test]
+    fn explanation() {
+        let input = input_generator(EXAMPLE).unwrap();
+        let invalid = find_invalid_number(&input, 5).unwrap();
+        assert_eq!((invalid.index, invalid.preamble), (14, 9..14));
+
+        let explanation = explain_with_preamble(&input, 2, 5).unwrap();
+        assert_eq!(explanation.answer, Answer::from(62));
+        assert_eq!(
+            explanation.steps,
+            vec![
+                "127 on line 15 is not the sum of two of the 5 numbers on lines 10 to 14",
+                "the 4 numbers on lines 3 to 6 sum to 127",
+                "smallest + largest = 15 + 47 = 62",
+            ]
+        );
+        let lines = explanation
+            .lines
+            .iter()
+            .map(|(line, _)| *line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![2, 3, 4, 5, 14]);
+        let error = explain_with_preamble(&input, 3, 5).unwrap_err();
+        assert_eq!(error.to_string(), "no part 3");
+    }
+
+    #[test]
+    fn weakness_edge_cases() {
+        // The target itself is not a range of two numbers
+        assert_eq!(find_weakness_range(&[1, 5, 2, 3], 5), Some(2..4));
+        // A range at the very end
+        assert_eq!(find_weakness_range(&[9, 1, 2], 3), Some(1..3));
+        assert_eq!(find_weakness_range(&[1, 2], 4), None);
+    }
+
+    #[test]
+    fn validation() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert!(validate_with_preamble(&input, 5).is_empty());
+        assert_eq!(
+            validate_with_preamble(&input[..5], 5),
+            vec!["expected more than 5 numbers, found 5"]
+        );
+        assert_eq!(
+            validate_with_preamble(&input[..14], 5),
+            vec!["every number is the sum of two of the 5 numbers before it"]
+        );
+        assert_eq!(
+            validate_with_preamble(&[1, 2, 5], 2),
+            vec!["no contiguous range of at least two numbers sums to 5 (line 3)"]
+        );
+    }
+}
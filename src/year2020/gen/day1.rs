@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+
+use super::Rng;
+
+/// Counts the pairs and the triples of `values` which sum to 2020.
+fn count_sums(values: &[i32]) -> (usize, usize) {
+    let mut pairs = 0;
+    let mut triples = 0;
+    for (i, &x) in values.iter().enumerate() {
+        for (j, &y) in values.iter().enumerate().skip(i + 1) {
+            if x + y == 2020 {
+                pairs += 1;
+            }
+            triples += values[j + 1..]
+                .iter()
+                .filter(|&&z| x + y + z == 2020)
+                .count();
+        }
+    }
+    (pairs, triples)
+}
+
+/// Generates an expense report with `size` entries (at most 1000),
+/// with exactly one pair and exactly one triple which sum to 2020.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(5, 1000);
+    // All other entries are larger than 1010, so two of them sum to more than 2020
+    let planted = loop {
+        let pair = rng.between(1, 1009) as i32;
+        let a = rng.between(1, 1009) as i32;
+        let b = rng.between(1, (2019 - a).min(1009) as i64) as i32;
+        let c = 2020 - a - b;
+        let planted = vec![pair, 2020 - pair, a, b, c];
+        let distinct = planted.iter().collect::<HashSet<_>>().len() == planted.len();
+        if c > 0 && distinct && count_sums(&planted) == (1, 1) {
+            break planted;
+        }
+    };
+    let small = planted.iter().filter(|&&x| x <= 1010).collect::<Vec<_>>();
+    let mut forbidden = planted.iter().copied().collect::<HashSet<_>>();
+    for (i, &&x) in small.iter().enumerate() {
+        forbidden.insert(2020 - x);
+        for &&y in &small[i + 1..] {
+            forbidden.insert(2020 - x - y);
+        }
+    }
+    let mut candidates = (1011..=2019)
+        .filter(|x| !forbidden.contains(x))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut candidates);
+    let mut expenses = planted;
+    expenses.extend(candidates.into_iter().take(size - expenses.len()));
+    rng.shuffle(&mut expenses);
+    expenses
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year2020::day1::{input_generator, part1, part2};
+
+    #[test]
+    fn unique_sums() {
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 200);
+            let expenses = input_generator(&input).unwrap();
+            assert_eq!(expenses.len(), 200);
+            assert_eq!(count_sums(&expenses), (1, 1));
+            assert!(part1(&expenses) > 0 && part2(&expenses) > 0);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use super::Rng;
+
+/// Generates `size` adapters (at most 100) whose joltages differ by 1 or 3 when sorted,
+/// with at most four differences of 1 in a row like the real input.
+/// More adapters could overflow the number of arrangements.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.min(100);
+    let mut joltages = vec![];
+    let mut joltage = 0;
+    let mut run = 0;
+    while joltages.len() < size {
+        if run < 4 && rng.chance(0.6) {
+            joltage += 1;
+            run += 1;
+        } else {
+            joltage += 3;
+            run = 0;
+        }
+        joltages.push(joltage);
+    }
+    rng.shuffle(&mut joltages);
+    joltages
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,13 @@
+use super::Rng;
+
+/// Generates a seat layout of `size` by `size` positions, with about seven seats in ten.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.7) { 'L' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,16 @@
+use super::Rng;
+
+/// Generates `size` navigation instructions. Turns are multiples of 90 degrees.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let action = *rng.choose(&['N', 'S', 'E', 'W', 'L', 'R', 'F', 'F']);
+            let value = match action {
+                'L' | 'R' => 90 * rng.between(1, 3),
+                _ => rng.between(1, 100),
+            };
+            format!("{}{}", action, value)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
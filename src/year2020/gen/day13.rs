@@ -0,0 +1,46 @@
+use super::Rng;
+use crate::util::is_prime;
+
+/// Generates a bus schedule with `size` entries, of which some are distinct prime bus IDs
+/// and the others are `x`.
+///
+/// The product of the bus IDs is limited, so that the answer of part 2 and the intermediate
+/// results of the iterative solver fit in an `i64`.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    let mut primes = (7..1000)
+        .filter(|&n| is_prime(n as u64))
+        .collect::<Vec<i64>>();
+    rng.shuffle(&mut primes);
+    let mut buses = vec![];
+    let mut product = 1i64;
+    for prime in primes {
+        if buses.len() == size || product > i64::MAX / 4 / prime {
+            break;
+        }
+        buses.push(prime);
+        product *= prime;
+    }
+    let mut schedule = vec!["x".to_owned(); size.max(buses.len())];
+    let mut positions = (1..schedule.len()).collect::<Vec<_>>();
+    rng.shuffle(&mut positions);
+    positions.insert(0, 0);
+    for (&position, bus) in positions.iter().zip(&buses) {
+        schedule[position] = bus.to_string();
+    }
+    format!("{}\n{}", rng.between(1000, 1_000_000), schedule.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year2020::day13::{input_generator, part2_crt, part2_iterative};
+
+    #[test]
+    fn solvers_agree() {
+        for seed in 0..50 {
+            let input = input_generator(&generate(&mut Rng::new(seed), 5 + seed as usize)).unwrap();
+            assert_eq!(part2_crt(&input), part2_iterative(&input), "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use super::Rng;
+
+/// Generates a program of `size` lines. Every mask has at most 6 floating bits,
+/// so that a write in part 2 affects at most 64 addresses.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut lines = vec![];
+    while lines.len() < size {
+        let floating = rng.below(7);
+        let mut bits = (0..36)
+            .map(|i| {
+                if i < floating {
+                    'X'
+                } else {
+                    *rng.choose(&['0', '1'])
+                }
+            })
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut bits);
+        lines.push(format!("mask = {}", bits.into_iter().collect::<String>()));
+        for _ in 0..rng.between(1, 5) {
+            let address = rng.between(0, 65535);
+            let value = rng.between(0, (1 << 36) - 1);
+            lines.push(format!("mem[{}] = {}", address, value));
+        }
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,13 @@
+use super::Rng;
+
+/// Generates `size` distinct starting numbers (at most 20).
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers = (0..20).collect::<Vec<_>>();
+    rng.shuffle(&mut numbers);
+    numbers
+        .iter()
+        .take(size)
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,69 @@
+use super::Rng;
+
+/// The width of the band of values of every field, see [`generate`].
+const BAND: i64 = 20;
+
+/// Generates a ticket notes with at most 20 fields and `size` nearby tickets,
+/// about a quarter of which contain an invalid value.
+///
+/// The fields can be assigned by elimination: the values of field `k` lie within the `k`-th band
+/// counting from the top, and field `k` accepts all values up to its band. Then exactly one field
+/// accepts the values of the last field, exactly two fields accept the values of the
+/// second-to-last field, and so on.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let fields = size.clamp(1, 20);
+    let mut names = vec![];
+    while names.len() < fields {
+        let name = if names.len() < 6 {
+            format!("departure {}", rng.word(3, 8))
+        } else {
+            format!("{} {}", rng.word(3, 8), rng.word(3, 8))
+        };
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    let max = |field: usize| BAND * (fields - field) as i64;
+    let rules = (0..fields)
+        .map(|field| {
+            let split = rng.between(1, max(field) - 1);
+            format!(
+                "{}: 1-{} or {}-{}",
+                names[field],
+                split,
+                split + 1,
+                max(field)
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut columns = (0..fields).collect::<Vec<_>>();
+    rng.shuffle(&mut columns);
+    let ticket = |rng: &mut Rng, invalid: bool| {
+        let mut values = columns
+            .iter()
+            .map(|&field| rng.between(max(field) - BAND + 1, max(field)))
+            .collect::<Vec<_>>();
+        if invalid {
+            let i = rng.below(values.len());
+            values[i] = rng.between(max(0) + 1, 999);
+        }
+        values
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let my_ticket = ticket(rng, false);
+    let nearby = (0..size)
+        .map(|_| {
+            let invalid = rng.chance(0.25);
+            ticket(rng, invalid)
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+        rules.join("\n"),
+        my_ticket,
+        nearby.join("\n")
+    )
+}
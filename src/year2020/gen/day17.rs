@@ -0,0 +1,13 @@
+use super::Rng;
+
+/// Generates an initial slice of `size` by `size` cubes, with about a third of them active.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.35) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,37 @@
+use super::Rng;
+
+/// The maximum number of operands in an expression, so that its value fits in an `i64`
+/// even if every operator is a multiplication.
+const MAX_OPERANDS: usize = 12;
+
+/// Generates an expression with at most `budget` operands, and subtracts the operands used.
+fn expression(rng: &mut Rng, depth: usize, budget: &mut usize) -> String {
+    let mut text = String::new();
+    let len = rng.between(2, 4);
+    for i in 0..len {
+        if *budget == 0 {
+            break;
+        }
+        if i > 0 {
+            text += if rng.chance(0.5) { " + " } else { " * " };
+        }
+        if depth < 2 && *budget >= 2 && rng.chance(0.3) {
+            text += &format!("({})", expression(rng, depth + 1, budget));
+        } else {
+            text += &rng.between(1, 9).to_string();
+            *budget -= 1;
+        }
+    }
+    text
+}
+
+/// Generates `size` expressions with single-digit operands and up to two levels of parentheses.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut budget = MAX_OPERANDS;
+            expression(rng, 0, &mut budget)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,49 @@
+use super::Rng;
+
+/// The rules, like in the real input, match one or more times rule 42
+/// followed by one or more times rule 31.
+/// Both rules match three letters: 42 starts with `a`, and 31 starts with `b`.
+const RULES: &str = "\
+0: 8 11
+1: \"a\"
+2: \"b\"
+3: 1 | 2
+4: 3 3
+8: 42
+11: 42 31
+31: 2 4
+42: 1 4";
+
+/// Generates the rules above and `size` messages.
+/// Most messages match the looping rules of part 2, some of them also match part 1.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let chunk = |rng: &mut Rng, first| {
+        let mut chunk = String::from(first);
+        for _ in 0..2 {
+            chunk.push(*rng.choose(&['a', 'b']));
+        }
+        chunk
+    };
+    let messages = (0..size)
+        .map(|_| {
+            let mut message = String::new();
+            if rng.chance(0.8) {
+                let n = rng.between(1, 3);
+                let m = rng.between(n + 1, 5);
+                for _ in 0..m {
+                    message += &chunk(rng, 'a');
+                }
+                for _ in 0..n {
+                    message += &chunk(rng, 'b');
+                }
+            } else {
+                for _ in 0..rng.between(1, 6) {
+                    let first = *rng.choose(&['a', 'b']);
+                    message += &chunk(rng, first);
+                }
+            }
+            message
+        })
+        .collect::<Vec<_>>();
+    format!("{}\n\n{}", RULES, messages.join("\n"))
+}
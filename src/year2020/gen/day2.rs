@@ -0,0 +1,23 @@
+use super::Rng;
+
+/// Generates `size` password policies with passwords,
+/// where the positions of every policy are within its password.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let letter = (b'a' + rng.below(26) as u8) as char;
+            let mut password = rng.word(3, 20).into_bytes();
+            // Make the letter occur more often than in a random word
+            for _ in 0..rng.below(password.len()) {
+                let i = rng.below(password.len());
+                password[i] = letter as u8;
+            }
+            let len = password.len() as i64;
+            let left = rng.between(1, len - 1);
+            let right = rng.between(left + 1, len);
+            let password = String::from_utf8(password).unwrap();
+            format!("{}-{} {}: {}", left, right, letter, password)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,153 @@
+use std::collections::HashSet;
+
+use super::Rng;
+
+const TILE_SIZE: usize = 10;
+
+const SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+type Pixels = Vec<Vec<bool>>;
+
+/// Edges indexed by row and column.
+type Edges = Vec<Vec<Vec<bool>>>;
+
+fn rotate(pixels: &Pixels) -> Pixels {
+    let size = pixels.len();
+    (0..size)
+        .map(|y| (0..size).map(|x| pixels[size - 1 - x][y]).collect())
+        .collect()
+}
+
+/// Returns the edge with its first and last pixels given by the corners,
+/// and random pixels in between.
+fn random_edge(rng: &mut Rng, first: bool, last: bool) -> Vec<bool> {
+    let mut edge = vec![first];
+    edge.extend((1..TILE_SIZE - 1).map(|_| rng.chance(0.5)));
+    edge.push(last);
+    edge
+}
+
+/// Generates the edges between the tiles, such that no two edges can be confused,
+/// even when flipped, or returns `None` if this failed.
+///
+/// The horizontal edges are indexed by the row of the tile below, the vertical edges by
+/// the column of the tile to the right. Neighbouring edges share the corner pixels.
+fn edges(rng: &mut Rng, n: usize) -> Option<(Edges, Edges)> {
+    let corners = (0..=n)
+        .map(|_| (0..=n).map(|_| rng.chance(0.5)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut used = HashSet::new();
+    let mut unique_edge = |rng: &mut Rng, first, last| {
+        for _ in 0..100 {
+            let edge = random_edge(rng, first, last);
+            let reversed = edge.iter().rev().copied().collect::<Vec<_>>();
+            if edge != reversed && !used.contains(&edge) && !used.contains(&reversed) {
+                used.insert(reversed);
+                used.insert(edge.clone());
+                return Some(edge);
+            }
+        }
+        None
+    };
+    let mut horizontal = vec![];
+    for row in &corners {
+        let edges = (0..n)
+            .map(|column| unique_edge(rng, row[column], row[column + 1]))
+            .collect::<Option<Vec<_>>>()?;
+        horizontal.push(edges);
+    }
+    let mut vertical = vec![];
+    for row in 0..n {
+        let edges = (0..=n)
+            .map(|column| unique_edge(rng, corners[row][column], corners[row + 1][column]))
+            .collect::<Option<Vec<_>>>()?;
+        vertical.push(edges);
+    }
+    Some((horizontal, vertical))
+}
+
+/// Generates an image with some noise and at least one sea monster.
+fn image(rng: &mut Rng, size: usize) -> Pixels {
+    let mut image = (0..size)
+        .map(|_| (0..size).map(|_| rng.chance(0.25)).collect())
+        .collect::<Pixels>();
+    let mut occupied = HashSet::new();
+    let monsters = 1 + rng.below(size * size / 200 + 1);
+    for _ in 0..monsters * 10 {
+        let x = rng.below(size - SEA_MONSTER[0].len() + 1);
+        let y = rng.below(size - SEA_MONSTER.len() + 1);
+        let pixels = SEA_MONSTER
+            .iter()
+            .enumerate()
+            .flat_map(|(dy, row)| {
+                row.char_indices()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(dx, _)| (x + dx, y + dy))
+            })
+            .collect::<Vec<_>>();
+        if pixels.iter().any(|pixel| occupied.contains(pixel)) {
+            continue;
+        }
+        for &(x, y) in &pixels {
+            image[y][x] = true;
+            occupied.insert((x, y));
+        }
+        if occupied.len() == monsters * pixels.len() {
+            break;
+        }
+    }
+    image
+}
+
+/// Generates a square of `n * n` tiles, where `n` is the square root of `size` (between 3 and 12).
+/// The tiles are shuffled, rotated and flipped, and their image contains sea monsters.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let n = ((size as f64).sqrt() as usize).clamp(3, 12);
+    let (horizontal, vertical) = loop {
+        if let Some(edges) = edges(rng, n) {
+            break edges;
+        }
+    };
+    let image = image(rng, n * (TILE_SIZE - 2));
+    let mut ids = (1000..10000).collect::<Vec<_>>();
+    rng.shuffle(&mut ids);
+    let mut tiles = vec![];
+    for row in 0..n {
+        for column in 0..n {
+            let mut pixels = vec![vec![false; TILE_SIZE]; TILE_SIZE];
+            for i in 0..TILE_SIZE {
+                pixels[0][i] = horizontal[row][column][i];
+                pixels[TILE_SIZE - 1][i] = horizontal[row + 1][column][i];
+                pixels[i][0] = vertical[row][column][i];
+                pixels[i][TILE_SIZE - 1] = vertical[row][column + 1][i];
+            }
+            for y in 1..TILE_SIZE - 1 {
+                for x in 1..TILE_SIZE - 1 {
+                    pixels[y][x] =
+                        image[row * (TILE_SIZE - 2) + y - 1][column * (TILE_SIZE - 2) + x - 1];
+                }
+            }
+            for _ in 0..rng.below(4) {
+                pixels = rotate(&pixels);
+            }
+            if rng.chance(0.5) {
+                pixels.reverse();
+            }
+            let pixels = pixels
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&p| if p { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+            tiles.push(format!("Tile {}:\n{}", ids[tiles.len()], pixels.join("\n")));
+        }
+    }
+    rng.shuffle(&mut tiles);
+    tiles.join("\n\n")
+}
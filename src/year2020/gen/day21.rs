@@ -0,0 +1,74 @@
+use std::collections::BTreeSet;
+
+use super::Rng;
+
+/// Returns `count` distinct words.
+fn words(rng: &mut Rng, count: usize) -> Vec<String> {
+    let mut words = vec![];
+    while words.len() < count {
+        let word = rng.word(4, 8);
+        if !words.contains(&word) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// Generates at least `size` foods, where every allergen is contained in exactly one ingredient.
+///
+/// Foods are added until the ingredient of every allergen can be determined,
+/// i.e. the only ingredient that all foods listing the allergen have in common is the one
+/// containing it.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let allergen_count = rng.between(2, 6) as usize;
+    let names = words(rng, allergen_count * 2 + 20);
+    let (allergens, ingredients) = names.split_at(allergen_count);
+    // The first ingredients contain the allergens, the others are safe
+    let mut foods = vec![];
+    let food = |rng: &mut Rng, listed: BTreeSet<usize>| {
+        let mut contents = ingredients
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| listed.contains(&i) || rng.chance(0.4))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut contents);
+        (contents, listed)
+    };
+    for _ in 0..size {
+        let mut listed = BTreeSet::new();
+        for _ in 0..rng.between(1, 3) {
+            listed.insert(rng.below(allergen_count));
+        }
+        foods.push(food(rng, listed));
+    }
+    for allergen in 0..allergen_count {
+        loop {
+            let mut common = (0..ingredients.len()).collect::<BTreeSet<_>>();
+            for (contents, listed) in &foods {
+                if listed.contains(&allergen) {
+                    common.retain(|i| contents.contains(i));
+                }
+            }
+            if common.len() == 1 {
+                break;
+            }
+            foods.push(food(rng, Some(allergen).into_iter().collect()));
+        }
+    }
+    foods
+        .iter()
+        .map(|(contents, listed)| {
+            let contents = contents
+                .iter()
+                .map(|&i| ingredients[i].as_str())
+                .collect::<Vec<_>>();
+            let listed = listed
+                .iter()
+                .map(|&i| allergens[i].as_str())
+                .collect::<Vec<_>>();
+            format!("{} (contains {})", contents.join(" "), listed.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
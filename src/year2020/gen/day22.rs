@@ -0,0 +1,20 @@
+use super::Rng;
+
+/// Generates two decks of `size` cards each (between 2 and 25), dealt from a shuffled deck.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(2, 25);
+    let mut cards = (1..=2 * size).collect::<Vec<_>>();
+    rng.shuffle(&mut cards);
+    let deck = |cards: &[usize]| {
+        cards
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    format!(
+        "Player 1:\n{}\n\nPlayer 2:\n{}",
+        deck(&cards[..size]),
+        deck(&cards[size..])
+    )
+}
@@ -0,0 +1,8 @@
+use super::Rng;
+
+/// Generates a circle of the cups labeled 1 to 9. The size is ignored.
+pub fn generate(rng: &mut Rng, _size: usize) -> String {
+    let mut cups = (1..=9).collect::<Vec<_>>();
+    rng.shuffle(&mut cups);
+    cups.iter().map(|x| x.to_string()).collect()
+}
@@ -0,0 +1,13 @@
+use super::Rng;
+
+/// Generates `size` paths of 5 to 20 steps.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..rng.between(5, 20))
+                .map(|_| *rng.choose(&["e", "se", "sw", "w", "nw", "ne"]))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
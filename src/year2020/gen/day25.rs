@@ -0,0 +1,13 @@
+use super::Rng;
+use crate::util::mod_pow;
+
+const MODULUS: u64 = 20201227;
+
+/// Generates the public keys of the card and the door,
+/// with loop sizes of at most 1000 times `size`.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max_loop_size = 1000 * size as i64;
+    let card = mod_pow(7, rng.between(1, max_loop_size) as u64, MODULUS);
+    let door = mod_pow(7, rng.between(1, max_loop_size) as u64, MODULUS);
+    format!("{}\n{}", card, door)
+}
@@ -0,0 +1,13 @@
+use super::Rng;
+
+/// Generates a map of 31 columns and `size` rows, with about one tree in five positions.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..31)
+                .map(|_| if rng.chance(0.2) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,99 @@
+use super::Rng;
+
+const EYE_COLORS: &[&str] = &["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+/// Returns a value for the given field which is valid for part 2, or an invalid one.
+fn field_value(rng: &mut Rng, key: &str, valid: bool) -> String {
+    let hex = |rng: &mut Rng, len| -> String {
+        (0..len)
+            .map(|_| std::char::from_digit(rng.below(16) as u32, 16).unwrap())
+            .collect()
+    };
+    match (key, valid) {
+        ("byr", true) => rng.between(1920, 2002).to_string(),
+        ("byr", false) => rng.between(2003, 2030).to_string(),
+        ("iyr", true) => rng.between(2010, 2020).to_string(),
+        ("iyr", false) => rng.between(1990, 2009).to_string(),
+        ("eyr", true) => rng.between(2020, 2030).to_string(),
+        ("eyr", false) => rng.between(2031, 2040).to_string(),
+        ("hgt", true) if rng.chance(0.5) => format!("{}cm", rng.between(150, 193)),
+        ("hgt", true) => format!("{}in", rng.between(59, 76)),
+        ("hgt", false) => format!("{}", rng.between(59, 193)),
+        ("hcl", true) => format!("#{}", hex(rng, 6)),
+        ("hcl", false) => hex(rng, 6),
+        ("ecl", true) => rng.choose(EYE_COLORS).to_string(),
+        ("ecl", false) => loop {
+            // A random word can happen to be a valid eye color
+            let color = rng.word(3, 3);
+            if !EYE_COLORS.contains(&color.as_str()) {
+                break color;
+            }
+        },
+        ("pid", true) => format!("{:09}", rng.between(0, 999_999_999)),
+        ("pid", false) => format!("{:010}", rng.between(0, 9_999_999_999)),
+        (_, _) => rng.between(1, 999).to_string(),
+    }
+}
+
+/// Generates `count` passports. About `valid_fraction` of them are valid for part 2,
+/// the others are either missing a required field or have one invalid value.
+pub fn passports(rng: &mut Rng, count: usize, valid_fraction: f64) -> String {
+    let keys = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+    (0..count)
+        .map(|_| {
+            let mut fields = keys.to_vec();
+            if rng.chance(0.5) {
+                fields.retain(|&key| key != "cid");
+            }
+            let mut invalid = None;
+            if !rng.chance(valid_fraction) {
+                let key = *rng.choose(&keys[..7]);
+                if rng.chance(0.5) {
+                    fields.retain(|&k| k != key);
+                } else {
+                    invalid = Some(key);
+                }
+            }
+            rng.shuffle(&mut fields);
+            let mut passport = String::new();
+            for (i, key) in fields.iter().enumerate() {
+                if i > 0 {
+                    passport.push(if rng.chance(0.3) { '\n' } else { ' ' });
+                }
+                let value = field_value(rng, key, invalid != Some(key));
+                passport.push_str(&format!("{}:{}", key, value));
+            }
+            passport
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Generates `size` passports, about half of which are valid.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    passports(rng, size, 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year2020::day4::{input_generator, part2};
+
+    #[test]
+    fn validity() {
+        let mut rng = Rng::new(4);
+        let all_valid = input_generator(&passports(&mut rng, 50, 1.0)).unwrap();
+        assert_eq!(part2(&all_valid), 50);
+        let all_invalid = input_generator(&passports(&mut rng, 50, 0.0)).unwrap();
+        assert_eq!(part2(&all_invalid), 0);
+    }
+
+    #[test]
+    fn invalid_eye_colors() {
+        let mut rng = Rng::new(4);
+        for _ in 0..20_000 {
+            let color = field_value(&mut rng, "ecl", false);
+            assert!(!EYE_COLORS.contains(&color.as_str()), "{}", color);
+        }
+    }
+}
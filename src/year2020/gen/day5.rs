@@ -0,0 +1,50 @@
+use super::Rng;
+
+fn boarding_pass(seat_id: usize) -> String {
+    (0..10)
+        .rev()
+        .map(|bit| {
+            let set = seat_id & (1 << bit) != 0;
+            match (bit >= 3, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            }
+        })
+        .collect()
+}
+
+/// Generates `size` boarding passes (at most 1000) for a contiguous range of seats,
+/// except for one missing seat in between.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(3, 1000);
+    let first = rng.below(1024 - size);
+    let missing = first + 1 + rng.below(size - 1);
+    let mut seats = (first..=first + size)
+        .filter(|&seat| seat != missing)
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut seats);
+    seats
+        .into_iter()
+        .map(boarding_pass)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year2020::day5::{input_generator, part2};
+
+    #[test]
+    fn missing_seat() {
+        assert_eq!(boarding_pass(357), "FBFBBFFRLR");
+        for seed in 0..10 {
+            let seats = input_generator(&generate(&mut Rng::new(seed), 100)).unwrap();
+            let missing = part2(&seats);
+            assert!(!seats.contains(&missing));
+            assert!(seats.contains(&(missing - 1)) && seats.contains(&(missing + 1)));
+        }
+    }
+}
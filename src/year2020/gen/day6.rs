@@ -0,0 +1,22 @@
+use super::Rng;
+
+/// Generates `size` groups of one to five people, each answering `yes` to distinct questions.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let questions = ('a'..='z').collect::<Vec<_>>();
+    (0..size)
+        .map(|_| {
+            let people = rng.between(1, 5);
+            (0..people)
+                .map(|_| {
+                    let mut answers = questions.clone();
+                    rng.shuffle(&mut answers);
+                    answers[..rng.between(1, 26) as usize]
+                        .iter()
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use super::Rng;
+
+/// Generates `size` bag rules, which form a directed acyclic graph.
+/// `shiny gold` is placed so that only a few bags are reachable from it,
+/// which keeps the answer of part 2 small enough.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    let mut colors = HashSet::new();
+    colors.insert("shiny gold".to_owned());
+    while colors.len() < size {
+        colors.insert(format!("{} {}", rng.word(3, 7), rng.word(3, 7)));
+    }
+    let mut colors = colors
+        .into_iter()
+        .filter(|color| color != "shiny gold")
+        .collect::<Vec<_>>();
+    colors.sort();
+    rng.shuffle(&mut colors);
+    let gold = size.saturating_sub(15).max(rng.below(size));
+    colors.insert(gold.min(colors.len()), "shiny gold".to_owned());
+
+    // Bags only contain bags which come later, so there are no cycles
+    let mut rules = (0..size)
+        .map(|i| {
+            let later = size - i - 1;
+            let count = rng.below(later.min(3) + 1);
+            let mut contents = HashSet::new();
+            while contents.len() < count {
+                contents.insert(i + 1 + rng.below(later.min(15)));
+            }
+            let mut contents = contents.into_iter().collect::<Vec<_>>();
+            contents.sort_unstable();
+            let contents = if contents.is_empty() {
+                "no other bags".to_owned()
+            } else {
+                contents
+                    .into_iter()
+                    .map(|j| {
+                        let amount = rng.between(1, 3);
+                        let bags = if amount == 1 { "bag" } else { "bags" };
+                        format!("{} {} {}", amount, colors[j], bags)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            format!("{} bags contain {}.", colors[i], contents)
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut rules);
+    rules.join("\n")
+}
@@ -0,0 +1,85 @@
+use super::Rng;
+
+/// Generates a program of `size` instructions which loops forever, and which terminates
+/// if exactly one `jmp` is changed into a `nop`.
+///
+/// The fixed program runs from top to bottom, with forward jumps only. All `nop`s except
+/// the corrupted instruction have argument `+0`, so they loop when changed into a `jmp`.
+/// The corrupted `jmp` jumps back to an instruction which leads to it again.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(4);
+    let mut program = vec![String::new(); size];
+    let mut executed = vec![];
+    let mut pc = 0;
+    while pc < size {
+        executed.push(pc);
+        let remaining = size - pc;
+        let choice = rng.below(10);
+        if choice < 2 && remaining > 2 {
+            let offset = rng.between(2, remaining.min(5) as i64) as usize;
+            program[pc] = format!("jmp +{}", offset);
+            pc += offset;
+        } else if choice < 4 {
+            program[pc] = "nop +0".to_owned();
+            pc += 1;
+        } else {
+            program[pc] = format!("acc {:+}", rng.between(-99, 99));
+            pc += 1;
+        }
+    }
+    // Skipped instructions are never executed, they can be anything
+    for instruction in program.iter_mut().filter(|i| i.is_empty()) {
+        *instruction = format!("acc {:+}", rng.between(-99, 99));
+    }
+    let corrupted = executed[1 + rng.below(executed.len() - 1)];
+    let target = *rng.choose(&executed[..executed.iter().position(|&i| i == corrupted).unwrap()]);
+    program[corrupted] = format!("jmp -{}", corrupted - target);
+    program.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year2020::day8::{input_generator, part1, part2};
+
+    /// Runs a program given as `(operation, argument)` pairs, and returns whether it terminates.
+    fn terminates(program: &[(&str, i32)]) -> bool {
+        let mut seen = vec![false; program.len()];
+        let mut pc = 0;
+        while pc < program.len() && !seen[pc] {
+            seen[pc] = true;
+            pc = match program[pc] {
+                ("jmp", offset) => (pc as i32 + offset) as usize,
+                _ => pc + 1,
+            };
+        }
+        pc >= program.len()
+    }
+
+    #[test]
+    fn single_fix() {
+        for seed in 0..20 {
+            let input = generate(&mut Rng::new(seed), 50);
+            let program = input
+                .lines()
+                .map(|line| (&line[..3], line[4..].parse().unwrap()))
+                .collect::<Vec<_>>();
+            assert!(!terminates(&program));
+            let fixes = (0..program.len())
+                .filter(|&i| {
+                    let mut fixed = program.clone();
+                    fixed[i].0 = match fixed[i].0 {
+                        "jmp" => "nop",
+                        "nop" => "jmp",
+                        _ => return false,
+                    };
+                    terminates(&fixed)
+                })
+                .count();
+            assert_eq!(fixes, 1, "{}", input);
+            let program = input_generator(&input).unwrap();
+            part1(&program);
+            part2(&program);
+        }
+    }
+}
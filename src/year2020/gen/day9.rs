@@ -0,0 +1,47 @@
+use super::Rng;
+
+const PREAMBLE: usize = 25;
+
+/// Returns whether `number` is the sum of two numbers in `previous`, like the solver checks it.
+fn is_sum(number: i64, previous: &[i64]) -> bool {
+    previous.iter().any(|&x| previous.contains(&(number - x)))
+}
+
+/// Generates `size` numbers (between 27 and 1000), where every number after the preamble of 25
+/// is the sum of two of the 25 numbers before it, except for the last one.
+/// The last number is the sum of a contiguous range of earlier numbers.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(PREAMBLE + 2, 1000);
+    let mut numbers = (1..=50).collect::<Vec<i64>>();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(PREAMBLE);
+    while numbers.len() < size - 1 {
+        // Add two of the smallest numbers, so that the numbers grow as slowly as possible
+        let mut previous = numbers[numbers.len() - PREAMBLE..].to_vec();
+        previous.sort_unstable();
+        previous.dedup();
+        let i = rng.below(5);
+        let j = loop {
+            let j = rng.below(6);
+            if j != i {
+                break j;
+            }
+        };
+        numbers.push(previous[i] + previous[j]);
+    }
+    let previous = numbers[numbers.len() - PREAMBLE..].to_vec();
+    let invalid = loop {
+        let len = rng.between(2, 5) as usize;
+        let start = rng.below(numbers.len() - len + 1);
+        let sum = numbers[start..start + len].iter().sum();
+        if !is_sum(sum, &previous) && !numbers.contains(&sum) {
+            break sum;
+        }
+    };
+    numbers.push(invalid);
+    numbers
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,173 @@
+//! Random puzzle inputs, for testing the solutions beyond the examples and the real inputs.
+//!
+//! Every day has a generator which produces a valid input, reproducible from a seed.
+//! Generated inputs satisfy the same guarantees as the real puzzle inputs, e.g. the expense
+//! report of day 1 has exactly one pair and one triple summing to 2020, so that alternative
+//! solvers can be checked against each other.
+
+mod day1;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day2;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+
+pub use day4::passports;
+
+/// A small pseudo-random number generator (SplitMix64).
+///
+/// Not suitable for cryptography, but fast and reproducible on every platform.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..n`. Panics if `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// Returns a number in `min..=max`.
+    pub fn between(&mut self, min: i64, max: i64) -> i64 {
+        assert!(min <= max, "empty range");
+        let span = (max - min) as u64 + 1;
+        min + ((self.next_u64() as u128 * span as u128) >> 64) as i64
+    }
+
+    /// Returns true with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// Returns a word of lowercase letters, with a length in `min_len..=max_len`.
+    pub fn word(&mut self, min_len: usize, max_len: usize) -> String {
+        let len = self.between(min_len as i64, max_len as i64);
+        (0..len)
+            .map(|_| (b'a' + self.below(26) as u8) as char)
+            .collect()
+    }
+}
+
+type Generator = fn(&mut Rng, usize) -> String;
+
+const GENERATORS: [Generator; 25] = [
+    day1::generate,
+    day2::generate,
+    day3::generate,
+    day4::generate,
+    day5::generate,
+    day6::generate,
+    day7::generate,
+    day8::generate,
+    day9::generate,
+    day10::generate,
+    day11::generate,
+    day12::generate,
+    day13::generate,
+    day14::generate,
+    day15::generate,
+    day16::generate,
+    day17::generate,
+    day18::generate,
+    day19::generate,
+    day20::generate,
+    day21::generate,
+    day22::generate,
+    day23::generate,
+    day24::generate,
+    day25::generate,
+];
+
+/// Generates a random input for the given day, or returns `None` if there is no such day.
+///
+/// `size` is the number of lines or items, such as expenses, passports or instructions.
+/// Some days limit the size, e.g. because the answer would overflow otherwise,
+/// and day 23 ignores it completely.
+pub fn random_input(day: u8, seed: u64, size: usize) -> Option<String> {
+    let generate = GENERATORS.get(usize::from(day).checked_sub(1)?)?;
+    Some(generate(&mut Rng::new(seed), size.max(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::solutions;
+
+    #[test]
+    fn rng() {
+        let mut rng = Rng::new(42);
+        let first = (0..10).map(|_| rng.next_u64()).collect::<Vec<_>>();
+        let mut rng = Rng::new(42);
+        assert_eq!(first, (0..10).map(|_| rng.next_u64()).collect::<Vec<_>>());
+        assert!((0..1000).all(|_| (3..=7).contains(&rng.between(3, 7))));
+        let mut items = (0..20).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    /// Runs every solution on small generated inputs.
+    /// The slow second parts of days 15 and 23 are skipped.
+    #[test]
+    fn all_days() {
+        for seed in 0..3 {
+            for solution in solutions() {
+                if matches!((solution.day, solution.part), (15, 2) | (23, 2)) {
+                    continue;
+                }
+                let input = random_input(solution.day, seed, 12).unwrap();
+                if let Err(e) = solution.run(&input) {
+                    panic!(
+                        "day {} part {} failed for seed {}: {}\n{}",
+                        solution.day, solution.part, seed, e, input
+                    );
+                }
+            }
+        }
+        assert_eq!(random_input(26, 0, 1), None);
+        assert_eq!(random_input(0, 0, 1), None);
+    }
+}
@@ -0,0 +1,100 @@
+//! The solutions for [Advent of Code 2020](https://adventofcode.com/2020).
+
+use std::error::Error;
+use std::io::Write;
+
+use crate::util::{Frames, ParseError, Recorder};
+use crate::{Answer, Explanation};
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod gen;
+
+pub const YEAR: u16 = 2020;
+
+/// Renders the simulation of a day from its puzzle input.
+pub type Renderer = fn(&str, &mut Frames) -> Result<(), Box<dyn Error>>;
+
+/// Returns the renderer for a day, if its solution can be rendered as images.
+pub fn renderer(day: u8) -> Option<Renderer> {
+    match day {
+        11 => Some(day11::render),
+        17 => Some(day17::render),
+        20 => Some(day20::render),
+        24 => Some(day24::render),
+        _ => None,
+    }
+}
+
+/// Runs a part of a day with tracing, and returns its answer. Parts other than 1 and 2 are errors.
+pub type Tracer = fn(&str, u8, &mut Recorder<Box<dyn Write>>) -> Result<Answer, Box<dyn Error>>;
+
+/// Returns the tracer for a day, if its simulation records trace events.
+pub fn tracer(day: u8) -> Option<Tracer> {
+    match day {
+        8 => Some(day8::trace),
+        11 => Some(day11::trace),
+        12 => Some(day12::trace),
+        14 => Some(day14::trace),
+        22 => Some(day22::trace),
+        23 => Some(day23::trace),
+        24 => Some(day24::trace),
+        _ => None,
+    }
+}
+
+/// Checks the structural invariants of the puzzle input of a day which the solutions rely on,
+/// and returns all violations.
+pub type Validator = fn(&str) -> Result<Vec<String>, ParseError>;
+
+/// Returns the input validation for a day, if it has one.
+pub fn validator(day: u8) -> Option<Validator> {
+    match day {
+        5 => Some(day5::validate),
+        8 => Some(day8::validate),
+        9 => Some(day9::validate),
+        12 => Some(day12::validate),
+        16 => Some(day16::validate),
+        18 => Some(day18::validate),
+        19 => Some(day19::validate),
+        20 => Some(day20::validate),
+        23 => Some(day23::validate),
+        25 => Some(day25::validate),
+        _ => None,
+    }
+}
+
+/// Explains how a part of a day arrives at its answer. Parts other than 1 and 2 are errors.
+pub type Explainer = fn(&str, u8) -> Result<Explanation, Box<dyn Error>>;
+
+/// Returns the explanation for a day, if it has one.
+pub fn explainer(day: u8) -> Option<Explainer> {
+    match day {
+        1 => Some(day1::explain),
+        9 => Some(day9::explain),
+        _ => None,
+    }
+}